@@ -0,0 +1,379 @@
+//! Support code for `assert_eq_approx!` and `assert_returns_approx!`, which compare
+//! floating point values (possibly nested in vectors) within a tolerance.
+
+use std::fmt::Display;
+
+/// default tolerance of the leetcode judge for problems returning doubles
+pub const LEETCODE_TOLERANCE: f64 = 1e-5;
+
+/// how far apart two values may be
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tolerance {
+    /// `|actual - expected| <= tolerance`
+    Absolute(f64),
+    /// `|actual - expected| <= tolerance * max(|actual|, |expected|)`
+    Relative(f64),
+}
+
+impl Tolerance {
+    fn limit(&self) -> f64 {
+        match *self {
+            Tolerance::Absolute(limit) | Tolerance::Relative(limit) => limit,
+        }
+    }
+
+    /// deviation of the values measured the way the tolerance is given
+    pub fn deviation(&self, actual: f64, expected: f64) -> f64 {
+        // same infinities and NaNs don't deviate
+        if actual == expected || (actual.is_nan() && expected.is_nan()) {
+            return 0.0;
+        }
+
+        let difference = (actual - expected).abs();
+        match self {
+            Tolerance::Absolute(_) => difference,
+            Tolerance::Relative(_) => difference / actual.abs().max(expected.abs()),
+        }
+    }
+}
+
+impl Display for Tolerance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Tolerance::Absolute(limit) => write!(f, "absolute tolerance {:e}", limit),
+            Tolerance::Relative(limit) => write!(f, "relative tolerance {:e}", limit),
+        }
+    }
+}
+
+/// the most deviating pair of values
+#[derive(Debug, Clone, PartialEq)]
+pub struct Deviation {
+    /// indices of the values, empty for scalars
+    pub path: Vec<usize>,
+    pub actual: f64,
+    pub expected: f64,
+    pub deviation: f64,
+}
+
+/// reason why values aren't approximately equal
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApproxError {
+    /// vectors at a given path have different lengths
+    LengthMismatch {
+        path: Vec<usize>,
+        actual: usize,
+        expected: usize,
+    },
+    /// the largest deviation exceeds the tolerance
+    OutOfTolerance {
+        deviation: Deviation,
+        tolerance: Tolerance,
+    },
+}
+
+/// `[1][2]` for the path `[1, 2]`, `value` for an empty path
+fn format_path(path: &[usize]) -> String {
+    if path.is_empty() {
+        "value".to_string()
+    } else {
+        path.iter().map(|index| format!("[{}]", index)).collect()
+    }
+}
+
+impl Display for ApproxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApproxError::LengthMismatch {
+                path,
+                actual,
+                expected,
+            } => write!(
+                f,
+                "lengths differ at {}: {} and {}",
+                format_path(path),
+                actual,
+                expected
+            ),
+            ApproxError::OutOfTolerance {
+                deviation,
+                tolerance,
+            } => write!(
+                f,
+                "max deviation {:e} at {}: {} and {} ({})",
+                deviation.deviation,
+                format_path(&deviation.path),
+                deviation.actual,
+                deviation.expected,
+                tolerance
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ApproxError {}
+
+/// values which are compared element by element within a tolerance
+pub trait ApproxValues {
+    /// visit pairs of the values, keeping the most deviating one in `max`
+    fn visit_pairs(
+        &self,
+        expected: &Self,
+        tolerance: Tolerance,
+        path: &mut Vec<usize>,
+        max: &mut Option<Deviation>,
+    ) -> Result<(), ApproxError>;
+}
+
+macro_rules! impl_approx_values_for_floats {
+    ($($type:ty),*) => {
+        $(
+            impl ApproxValues for $type {
+                fn visit_pairs(
+                    &self,
+                    expected: &Self,
+                    tolerance: Tolerance,
+                    path: &mut Vec<usize>,
+                    max: &mut Option<Deviation>,
+                ) -> Result<(), ApproxError> {
+                    let (actual, expected) = (f64::from(*self), f64::from(*expected));
+                    let deviation = tolerance.deviation(actual, expected);
+
+                    // NaN deviations are always kept
+                    let is_max = match max {
+                        Some(max) => deviation > max.deviation || deviation.is_nan(),
+                        None => true,
+                    };
+                    if is_max {
+                        *max = Some(Deviation {
+                            path: path.clone(),
+                            actual,
+                            expected,
+                            deviation,
+                        });
+                    }
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_approx_values_for_floats!(f32, f64);
+
+impl<T: ApproxValues> ApproxValues for [T] {
+    fn visit_pairs(
+        &self,
+        expected: &Self,
+        tolerance: Tolerance,
+        path: &mut Vec<usize>,
+        max: &mut Option<Deviation>,
+    ) -> Result<(), ApproxError> {
+        if self.len() != expected.len() {
+            return Err(ApproxError::LengthMismatch {
+                path: path.clone(),
+                actual: self.len(),
+                expected: expected.len(),
+            });
+        }
+
+        for (index, (actual, expected)) in self.iter().zip(expected).enumerate() {
+            path.push(index);
+            let result = actual.visit_pairs(expected, tolerance, path, max);
+            path.pop();
+            result?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: ApproxValues> ApproxValues for Vec<T> {
+    fn visit_pairs(
+        &self,
+        expected: &Self,
+        tolerance: Tolerance,
+        path: &mut Vec<usize>,
+        max: &mut Option<Deviation>,
+    ) -> Result<(), ApproxError> {
+        self.as_slice()
+            .visit_pairs(expected.as_slice(), tolerance, path, max)
+    }
+}
+
+impl<T: ApproxValues, const N: usize> ApproxValues for [T; N] {
+    fn visit_pairs(
+        &self,
+        expected: &Self,
+        tolerance: Tolerance,
+        path: &mut Vec<usize>,
+        max: &mut Option<Deviation>,
+    ) -> Result<(), ApproxError> {
+        self.as_slice()
+            .visit_pairs(expected.as_slice(), tolerance, path, max)
+    }
+}
+
+/// the most deviating pair of values, or `None` if there are no values
+pub fn max_deviation<T: ApproxValues + ?Sized>(
+    actual: &T,
+    expected: &T,
+    tolerance: Tolerance,
+) -> Result<Option<Deviation>, ApproxError> {
+    let mut max = None;
+    actual.visit_pairs(expected, tolerance, &mut vec![], &mut max)?;
+    Ok(max)
+}
+
+/// check that the values have the same shape and deviate within the tolerance
+pub fn check_approx<T: ApproxValues + ?Sized>(
+    actual: &T,
+    expected: &T,
+    tolerance: Tolerance,
+) -> Result<(), ApproxError> {
+    match max_deviation(actual, expected, tolerance)? {
+        // NaN deviations are out of any tolerance
+        Some(deviation)
+            if deviation.deviation > tolerance.limit() || deviation.deviation.is_nan() =>
+        {
+            Err(ApproxError::OutOfTolerance {
+                deviation,
+                tolerance,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Tolerance::Absolute(1e-5), 1.0, 1.000001, true)]
+    #[case(Tolerance::Absolute(1e-5), 1.0, 1.0001, false)]
+    #[case(Tolerance::Absolute(1e-5), 1e10, 1e10 + 1.0, false)]
+    #[case(Tolerance::Relative(1e-9), 1e10, 1e10 + 1.0, true)]
+    #[case(Tolerance::Relative(1e-9), 0.0, 1e-12, false)]
+    #[case(Tolerance::Relative(1e-9), 0.0, 0.0, true)]
+    #[case(Tolerance::Absolute(1e-5), f64::INFINITY, f64::INFINITY, true)]
+    #[case(Tolerance::Absolute(1e-5), f64::NAN, f64::NAN, true)]
+    #[case(Tolerance::Absolute(1e-5), f64::NAN, 1.0, false)]
+    fn scalars_are_compared(
+        #[case] tolerance: Tolerance,
+        #[case] actual: f64,
+        #[case] expected: f64,
+        #[case] is_approx: bool,
+    ) {
+        assert_eq!(
+            check_approx(&actual, &expected, tolerance).is_ok(),
+            is_approx
+        );
+    }
+
+    #[test]
+    fn max_deviation_is_found() {
+        let actual = vec![vec![1.0, 2.0], vec![3.0, 4.5], vec![5.0, 6.25]];
+        let expected = vec![vec![1.0, 2.0], vec![3.0, 4.0], vec![5.0, 6.0]];
+
+        let deviation = max_deviation(&actual, &expected, Tolerance::Absolute(1e-5)).unwrap();
+        assert_eq!(
+            deviation,
+            Some(Deviation {
+                path: vec![1, 1],
+                actual: 4.5,
+                expected: 4.0,
+                deviation: 0.5,
+            })
+        );
+        assert_eq!(
+            max_deviation::<[f64]>(&[], &[], Tolerance::Absolute(1e-5)),
+            Ok(None)
+        );
+    }
+
+    #[rstest]
+    #[case(
+        vec![vec![1.0], vec![2.0, 3.5]],
+        vec![vec![1.0], vec![2.0, 3.0]],
+        Tolerance::Absolute(1e-5),
+        "max deviation 5e-1 at [1][1]: 3.5 and 3 (absolute tolerance 1e-5)"
+    )]
+    #[case(
+        vec![vec![1.0], vec![2.0, 3.0]],
+        vec![vec![1.0], vec![2.0]],
+        Tolerance::Absolute(1e-5),
+        "lengths differ at [1]: 2 and 1"
+    )]
+    #[case(
+        vec![vec![4.0]],
+        vec![vec![5.0]],
+        Tolerance::Relative(0.1),
+        "max deviation 2e-1 at [0][0]: 4 and 5 (relative tolerance 1e-1)"
+    )]
+    fn errors_are_described(
+        #[case] actual: Vec<Vec<f64>>,
+        #[case] expected: Vec<Vec<f64>>,
+        #[case] tolerance: Tolerance,
+        #[case] message: &str,
+    ) {
+        let error = check_approx(&actual, &expected, tolerance).unwrap_err();
+        assert_eq!(error.to_string(), message);
+    }
+
+    #[test]
+    fn scalar_errors_are_described() {
+        let error = check_approx(&0.5f32, &0.25, Tolerance::Absolute(1e-5)).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "max deviation 2.5e-1 at value: 0.5 and 0.25 (absolute tolerance 1e-5)"
+        );
+    }
+
+    #[test]
+    fn crate_assert_eq_approx_accepts_close_values() {
+        crate::assert_eq_approx!(0.1 + 0.2, 0.3);
+        crate::assert_eq_approx!(vec![1.0 / 3.0, 2.0], vec![0.33333, 2.0], abs = 1e-5);
+        crate::assert_eq_approx!(1e20 + 1e5, 1e20, rel = 1e-9);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed:\n`left == right` (approximately)\nwhere\n  left: `[[0.5, 1.5]]`\n  right: `[[0.5, 1.0]]`\ndifference: max deviation 5e-1 at [0][1]: 1.5 and 1 (absolute tolerance 1e-5)"
+    )]
+    fn crate_assert_eq_approx_reports_max_deviation() {
+        let left = vec![vec![0.5, 1.5]];
+        let right = vec![vec![0.5, 1.0]];
+        crate::assert_eq_approx!(left, right);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "where\n  values.next().unwrap(): `1.0`\ndifference: max deviation 1e0"
+    )]
+    fn crate_assert_eq_approx_evaluates_arguments_once() {
+        let mut values = [0.5, 1.0].into_iter();
+        crate::assert_eq_approx!(values.next().unwrap(), 0.5);
+        crate::assert_eq_approx!(values.next().unwrap(), 2.0);
+    }
+
+    #[test]
+    fn crate_assert_returns_approx_accepts_close_values() {
+        fn average(values: Vec<i32>) -> f64 {
+            values.iter().sum::<i32>() as f64 / values.len() as f64
+        }
+        crate::assert_returns_approx!(0.66667, average, vec![0, 1, 1]);
+        crate::assert_returns_approx!(rel = 1e-3, 0.6667, average, vec![0, 1, 1]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "average([0,1]) returned 0.50000\nexpected result: 0.75000\ndifference: max deviation 2.5e-1 at value: 0.5 and 0.75 (absolute tolerance 1e-3)"
+    )]
+    fn crate_assert_returns_approx_reports_max_deviation() {
+        fn average(values: Vec<i32>) -> f64 {
+            values.iter().sum::<i32>() as f64 / values.len() as f64
+        }
+        crate::assert_returns_approx!(abs = 1e-3, 0.75, average, vec![0, 1]);
+    }
+}
@@ -0,0 +1,338 @@
+use std::ops::{Index, IndexMut};
+
+pub type NodeID = usize;
+
+/// Singly linked lists storing their nodes in a single vector and linking them by indices.
+///
+/// Unlike `ListNode`, nodes may have several predecessors, so lists can share tails
+/// and contain cycles. Passing an id which doesn't belong to the arena panics
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArenaList<T> {
+    values: Vec<T>,
+    next: Vec<Option<NodeID>>,
+}
+
+/// cycle found in a list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// first node of the list which belongs to the cycle
+    pub start: NodeID,
+    /// position of the first cycle node in the list (counting from 0)
+    pub start_index: usize,
+    /// number of nodes in the cycle
+    pub len: usize,
+}
+
+impl<T> ArenaList<T> {
+    /// create a new empty arena
+    pub fn new() -> Self {
+        ArenaList {
+            values: vec![],
+            next: vec![],
+        }
+    }
+
+    /// build a list whose tail links back to the node at `pos` (leetcode "linked list cycle" input).
+    /// Returns the arena and the head of the list
+    pub fn with_cycle(values: Vec<T>, pos: Option<usize>) -> (Self, Option<NodeID>) {
+        let len = values.len();
+        let mut arena = ArenaList::new();
+        let head = arena.push_list(values);
+
+        if let Some(pos) = pos {
+            assert!(pos < len, "cycle position {} is out of bounds", pos);
+            arena.set_next(len - 1, Some(pos));
+        }
+
+        (arena, head)
+    }
+
+    /// number of nodes in the arena
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// check if the arena has no nodes
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// add a new node without a successor
+    pub fn add_node(&mut self, val: T) -> NodeID {
+        self.values.push(val);
+        self.next.push(None);
+        self.values.len() - 1
+    }
+
+    /// add a chain of new nodes with consecutive ids, returning its head
+    pub fn push_list(&mut self, values: Vec<T>) -> Option<NodeID> {
+        let head = self.len();
+        for val in values {
+            let id = self.add_node(val);
+            if id > head {
+                self.set_next(id - 1, Some(id));
+            }
+        }
+        (head < self.len()).then_some(head)
+    }
+
+    /// get the id of the node's successor
+    pub fn next(&self, id: NodeID) -> Option<NodeID> {
+        self.next[id]
+    }
+
+    /// link the node to a new successor
+    pub fn set_next(&mut self, id: NodeID, next: Option<NodeID>) {
+        self.next[id] = next;
+    }
+
+    /// iterate over node ids starting from the head. Never ends for a cyclic list
+    pub fn nodes(&self, head: Option<NodeID>) -> impl Iterator<Item = NodeID> + '_ {
+        std::iter::successors(head, move |&id| self.next(id))
+    }
+
+    /// find a cycle using Floyd's tortoise and hare algorithm
+    pub fn floyd_cycle(&self, head: Option<NodeID>) -> Option<Cycle> {
+        let mut slow = head?;
+        let mut fast = head?;
+
+        loop {
+            slow = self.next(slow)?;
+            fast = self.next(self.next(fast)?)?;
+            if slow == fast {
+                break;
+            }
+        }
+
+        // the distance from the head to the cycle start equals
+        // the distance from the meeting point to it modulo the cycle length
+        let mut start = head?;
+        let mut start_index = 0;
+        while start != slow {
+            start = self.next(start)?;
+            slow = self.next(slow)?;
+            start_index += 1;
+        }
+
+        let len = self.nodes(self.next(start)).position(|id| id == start)? + 1;
+
+        Some(Cycle {
+            start,
+            start_index,
+            len,
+        })
+    }
+
+    /// find a cycle using Brent's algorithm
+    pub fn brent_cycle(&self, head: Option<NodeID>) -> Option<Cycle> {
+        // the tortoise waits at powers of two while the hare looks for it
+        let mut power = 1;
+        let mut len = 1;
+        let mut tortoise = head?;
+        let mut hare = self.next(tortoise)?;
+
+        while tortoise != hare {
+            if power == len {
+                tortoise = hare;
+                power *= 2;
+                len = 0;
+            }
+            hare = self.next(hare)?;
+            len += 1;
+        }
+
+        // a pointer `len` nodes ahead meets the one from the head at the cycle start
+        let mut start = head?;
+        let mut ahead = self.nodes(head).nth(len)?;
+        let mut start_index = 0;
+        while start != ahead {
+            start = self.next(start)?;
+            ahead = self.next(ahead)?;
+            start_index += 1;
+        }
+
+        Some(Cycle {
+            start,
+            start_index,
+            len,
+        })
+    }
+
+    /// find the first node shared by two acyclic lists
+    pub fn intersection(&self, head_a: Option<NodeID>, head_b: Option<NodeID>) -> Option<NodeID> {
+        // both pointers walk through both lists, so they reach the shared part at the same time
+        let mut a = head_a;
+        let mut b = head_b;
+
+        while a != b {
+            a = match a {
+                Some(id) => self.next(id),
+                None => head_b,
+            };
+            b = match b {
+                Some(id) => self.next(id),
+                None => head_a,
+            };
+        }
+
+        a
+    }
+}
+
+impl<T: PartialEq> ArenaList<T> {
+    /// build two lists which share the nodes after the first `skip_a` nodes of the first list
+    /// and the first `skip_b` nodes of the second one (leetcode "intersection of two lists" input).
+    /// Returns the arena and the heads of both lists
+    pub fn with_intersection(
+        list_a: Vec<T>,
+        mut list_b: Vec<T>,
+        skip_a: usize,
+        skip_b: usize,
+    ) -> (Self, Option<NodeID>, Option<NodeID>) {
+        assert!(
+            skip_a <= list_a.len() && skip_b <= list_b.len(),
+            "skips are out of bounds"
+        );
+        assert!(
+            list_a[skip_a..] == list_b[skip_b..],
+            "tails of the lists after skipped nodes differ"
+        );
+        list_b.truncate(skip_b);
+
+        let mut arena = ArenaList::new();
+        let head_a = arena.push_list(list_a);
+
+        let shared = arena.nodes(head_a).nth(skip_a);
+        let head_b = arena.push_list(list_b);
+        let tail_b = arena.nodes(head_b).last();
+        match tail_b {
+            Some(tail_b) => arena.set_next(tail_b, shared),
+            None => return (arena, head_a, shared),
+        }
+
+        (arena, head_a, head_b)
+    }
+}
+
+impl<T> Index<NodeID> for ArenaList<T> {
+    type Output = T;
+
+    fn index(&self, id: NodeID) -> &T {
+        &self.values[id]
+    }
+}
+
+impl<T> IndexMut<NodeID> for ArenaList<T> {
+    fn index_mut(&mut self, id: NodeID) -> &mut T {
+        &mut self.values[id]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    fn values(arena: &ArenaList<i32>, head: Option<NodeID>, limit: usize) -> Vec<i32> {
+        arena.nodes(head).take(limit).map(|id| arena[id]).collect()
+    }
+
+    #[rstest]
+    #[case(vec![3, 2, 0, -4], Some(1), Some((1, 3)))]
+    #[case(vec![1, 2], Some(0), Some((0, 2)))]
+    #[case(vec![1], Some(0), Some((0, 1)))]
+    #[case(vec![1], None, None)]
+    #[case(vec![], None, None)]
+    fn cycle_detection(
+        #[case] input: Vec<i32>,
+        #[case] pos: Option<usize>,
+        #[case] expected: Option<(usize, usize)>,
+    ) {
+        let (arena, head) = ArenaList::with_cycle(input, pos);
+
+        let floyd = arena.floyd_cycle(head);
+        let brent = arena.brent_cycle(head);
+        assert_eq!(floyd, brent);
+        assert_eq!(floyd.map(|cycle| (cycle.start_index, cycle.len)), expected);
+    }
+
+    #[test]
+    fn cyclic_list_walks_forever() {
+        let (arena, head) = ArenaList::with_cycle(vec![3, 2, 0, -4], Some(1));
+        assert_eq!(values(&arena, head, 7), vec![3, 2, 0, -4, 2, 0, -4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "cycle position 2 is out of bounds")]
+    fn cycle_position_is_checked() {
+        ArenaList::with_cycle(vec![1, 2], Some(2));
+    }
+
+    #[rstest]
+    #[case(vec![4, 1, 8, 4, 5], vec![5, 6, 1, 8, 4, 5], 2, 3, Some(8))]
+    #[case(vec![1, 9, 1, 2, 4], vec![3, 2, 4], 3, 1, Some(2))]
+    #[case(vec![2, 6, 4], vec![1, 5], 3, 2, None)]
+    #[case(vec![1, 2], vec![1, 2], 0, 0, Some(1))]
+    fn intersection(
+        #[case] list_a: Vec<i32>,
+        #[case] list_b: Vec<i32>,
+        #[case] skip_a: usize,
+        #[case] skip_b: usize,
+        #[case] expected: Option<i32>,
+    ) {
+        let (arena, head_a, head_b) =
+            ArenaList::with_intersection(list_a.clone(), list_b.clone(), skip_a, skip_b);
+
+        assert_eq!(values(&arena, head_a, 10), list_a);
+        assert_eq!(values(&arena, head_b, 10), list_b);
+
+        let shared = arena.intersection(head_a, head_b);
+        assert_eq!(shared, arena.nodes(head_a).nth(skip_a));
+        assert_eq!(shared.map(|id| arena[id]), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "tails of the lists after skipped nodes differ")]
+    fn intersection_tails_are_checked() {
+        ArenaList::with_intersection(vec![1, 2], vec![3], 1, 0);
+    }
+
+    proptest! {
+        #[test]
+        fn floyd_and_brent_find_the_cycle(
+            input in vec(-100..100, 1..50),
+            pos in any::<prop::sample::Index>(),
+            has_cycle in any::<bool>(),
+        ) {
+            let len = input.len();
+            let pos = has_cycle.then(|| pos.index(len));
+            let (arena, head) = ArenaList::with_cycle(input, pos);
+
+            let expected = pos.map(|pos| Cycle {
+                start: arena.nodes(head).nth(pos).unwrap(),
+                start_index: pos,
+                len: len - pos,
+            });
+            prop_assert_eq!(arena.floyd_cycle(head), expected);
+            prop_assert_eq!(arena.brent_cycle(head), expected);
+        }
+
+        #[test]
+        fn intersection_is_found(
+            only_a in vec(-100..100, 0..10),
+            only_b in vec(-100..100, 0..10),
+            shared in vec(-100..100, 0..10),
+        ) {
+            let list_a: Vec<i32> = only_a.iter().chain(shared.iter()).copied().collect();
+            let list_b: Vec<i32> = only_b.iter().chain(shared.iter()).copied().collect();
+            let (arena, head_a, head_b) =
+                ArenaList::with_intersection(list_a, list_b, only_a.len(), only_b.len());
+
+            let found = arena.intersection(head_a, head_b);
+            prop_assert_eq!(found, arena.nodes(head_b).nth(only_b.len()));
+            prop_assert_eq!(values(&arena, found, 20), shared);
+        }
+    }
+}
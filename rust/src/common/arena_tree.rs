@@ -0,0 +1,459 @@
+use std::cell::RefCell;
+use std::ops::{Index, IndexMut};
+use std::rc::Rc;
+
+use crate::common::binary_tree::{NodeLink, TreeNode};
+
+pub type NodeID = usize;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ArenaNode<T> {
+    val: T,
+    parent: Option<NodeID>,
+    left: Option<NodeID>,
+    right: Option<NodeID>,
+}
+
+/// Binary tree storing its nodes in a single vector and linking them by indices.
+///
+/// Nodes are never removed from the arena: detached subtrees stay in it
+/// and can be attached back, but aren't a part of the tree until then.
+/// Passing an id which doesn't belong to the arena panics
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArenaTree<T> {
+    nodes: Vec<ArenaNode<T>>,
+    root: Option<NodeID>,
+}
+
+impl<T> ArenaTree<T> {
+    /// create a new empty tree
+    pub fn new() -> Self {
+        ArenaTree {
+            nodes: vec![],
+            root: None,
+        }
+    }
+
+    /// create a tree with a single root node
+    pub fn with_root(val: T) -> Self {
+        let mut tree = ArenaTree::new();
+        let root = tree.add_node(val);
+        tree.root = Some(root);
+        tree
+    }
+
+    /// get the id of the root
+    pub fn root(&self) -> Option<NodeID> {
+        self.root
+    }
+
+    /// make the detached node a new root, detaching the old one
+    pub fn set_root(&mut self, id: Option<NodeID>) -> Option<NodeID> {
+        if let Some(id) = id {
+            self.assert_detached(id);
+        }
+        std::mem::replace(&mut self.root, id)
+    }
+
+    /// number of nodes in the arena, including the detached ones
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// check if the arena has no nodes
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// add a new detached node into the arena
+    pub fn add_node(&mut self, val: T) -> NodeID {
+        self.nodes.push(ArenaNode {
+            val,
+            parent: None,
+            left: None,
+            right: None,
+        });
+        self.nodes.len() - 1
+    }
+
+    /// get the id of the node's parent
+    pub fn parent(&self, id: NodeID) -> Option<NodeID> {
+        self.nodes[id].parent
+    }
+
+    /// get the id of the node's left child
+    pub fn left(&self, id: NodeID) -> Option<NodeID> {
+        self.nodes[id].left
+    }
+
+    /// get the id of the node's right child
+    pub fn right(&self, id: NodeID) -> Option<NodeID> {
+        self.nodes[id].right
+    }
+
+    /// get the id of the other child of the node's parent
+    pub fn sibling(&self, id: NodeID) -> Option<NodeID> {
+        let parent = &self.nodes[self.parent(id)?];
+        if parent.left == Some(id) {
+            parent.right
+        } else {
+            parent.left
+        }
+    }
+
+    /// iterator over ids of the node's existing children, left one first
+    pub fn children(&self, id: NodeID) -> impl Iterator<Item = NodeID> + '_ {
+        self.left(id).into_iter().chain(self.right(id))
+    }
+
+    /// check if the node has no children
+    pub fn is_leaf(&self, id: NodeID) -> bool {
+        self.left(id).is_none() && self.right(id).is_none()
+    }
+
+    /// attach the detached node (or nothing) as a left child.
+    /// Returns the id of the previous left child which becomes detached
+    pub fn set_left(&mut self, id: NodeID, child: Option<NodeID>) -> Option<NodeID> {
+        let previous = self.nodes[id].left;
+        self.link_child(id, child, previous);
+        self.nodes[id].left = child;
+        previous
+    }
+
+    /// attach the detached node (or nothing) as a right child.
+    /// Returns the id of the previous right child which becomes detached
+    pub fn set_right(&mut self, id: NodeID, child: Option<NodeID>) -> Option<NodeID> {
+        let previous = self.nodes[id].right;
+        self.link_child(id, child, previous);
+        self.nodes[id].right = child;
+        previous
+    }
+
+    /// add a new node as a left child, detaching the previous one
+    pub fn insert_left(&mut self, id: NodeID, val: T) -> NodeID {
+        let child = self.add_node(val);
+        self.set_left(id, Some(child));
+        child
+    }
+
+    /// add a new node as a right child, detaching the previous one
+    pub fn insert_right(&mut self, id: NodeID, val: T) -> NodeID {
+        let child = self.add_node(val);
+        self.set_right(id, Some(child));
+        child
+    }
+
+    /// cut the subtree of the node from its parent (or from the tree if it's the root)
+    pub fn detach(&mut self, id: NodeID) {
+        match self.parent(id) {
+            Some(parent) if self.left(parent) == Some(id) => {
+                self.set_left(parent, None);
+            }
+            Some(parent) => {
+                self.set_right(parent, None);
+            }
+            None if self.root == Some(id) => {
+                self.root = None;
+            }
+            None => {}
+        }
+    }
+
+    /// swap left and right children of the node
+    pub fn swap_children(&mut self, id: NodeID) {
+        let node = &mut self.nodes[id];
+        std::mem::swap(&mut node.left, &mut node.right);
+    }
+
+    /// ids of the nodes reachable from the root in pre-order
+    pub fn preorder(&self) -> Vec<NodeID> {
+        let mut result = vec![];
+        let mut stack: Vec<NodeID> = self.root.into_iter().collect();
+
+        while let Some(id) = stack.pop() {
+            result.push(id);
+            stack.extend(self.right(id));
+            stack.extend(self.left(id));
+        }
+
+        result
+    }
+
+    fn link_child(&mut self, id: NodeID, child: Option<NodeID>, previous: Option<NodeID>) {
+        if let Some(child) = child {
+            self.assert_detached(child);
+            self.assert_not_ancestor(child, id);
+        }
+        if let Some(previous) = previous {
+            self.nodes[previous].parent = None;
+        }
+        if let Some(child) = child {
+            self.nodes[child].parent = Some(id);
+        }
+    }
+
+    fn assert_detached(&self, id: NodeID) {
+        assert!(
+            self.nodes[id].parent.is_none() && self.root != Some(id),
+            "node {} is already attached",
+            id
+        );
+    }
+
+    /// panic if the node is the other one or one of its ancestors, since linking them makes a cycle
+    fn assert_not_ancestor(&self, ancestor: NodeID, id: NodeID) {
+        // a leaf can only be an ancestor of itself, which keeps building trees top-down linear
+        let mut current = if self.is_leaf(ancestor) && ancestor != id {
+            None
+        } else {
+            Some(id)
+        };
+        while let Some(node) = current {
+            assert!(
+                node != ancestor,
+                "node {} can't be attached under its descendant {}",
+                ancestor,
+                id
+            );
+            current = self.parent(node);
+        }
+    }
+}
+
+impl<T: Clone> ArenaTree<T> {
+    /// copy a linked tree into an arena, assigning ids to the nodes in pre-order
+    pub fn from_link(root: &NodeLink<T>) -> Self {
+        let mut tree = ArenaTree::new();
+
+        // (node, id of its parent, is it a left child)
+        let mut stack = vec![];
+        stack.extend(root.clone().map(|node| (node, None, false)));
+
+        while let Some((node, parent, is_left)) = stack.pop() {
+            let node_ref = node.borrow();
+            let id = tree.add_node(node_ref.val.clone());
+
+            match parent {
+                None => tree.root = Some(id),
+                Some(parent) if is_left => {
+                    tree.set_left(parent, Some(id));
+                }
+                Some(parent) => {
+                    tree.set_right(parent, Some(id));
+                }
+            }
+
+            stack.extend(node_ref.right.clone().map(|child| (child, Some(id), false)));
+            stack.extend(node_ref.left.clone().map(|child| (child, Some(id), true)));
+        }
+
+        tree
+    }
+
+    /// copy the nodes reachable from the root into a linked tree
+    pub fn to_link(&self) -> NodeLink<T> {
+        let mut links: Vec<NodeLink<T>> = vec![None; self.len()];
+
+        let order = self.preorder();
+        for &id in order.iter() {
+            links[id] = TreeNode::new_link(self[id].clone(), None, None);
+        }
+
+        for &id in order.iter() {
+            let mut node = links[id].as_ref().unwrap().borrow_mut();
+            node.left = self.left(id).and_then(|child| links[child].clone());
+            node.right = self.right(id).and_then(|child| links[child].clone());
+        }
+
+        self.root.and_then(|root| links[root].clone())
+    }
+}
+
+impl<T> Default for ArenaTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Index<NodeID> for ArenaTree<T> {
+    type Output = T;
+
+    fn index(&self, id: NodeID) -> &T {
+        &self.nodes[id].val
+    }
+}
+
+impl<T> IndexMut<NodeID> for ArenaTree<T> {
+    fn index_mut(&mut self, id: NodeID) -> &mut T {
+        &mut self.nodes[id].val
+    }
+}
+
+impl<T: Clone> From<&NodeLink<T>> for ArenaTree<T> {
+    fn from(root: &NodeLink<T>) -> Self {
+        ArenaTree::from_link(root)
+    }
+}
+
+/// helper type for naming a node of a linked tree
+type LinkedNode<T> = Rc<RefCell<TreeNode<T>>>;
+
+impl<T: Clone> From<&ArenaTree<T>> for Option<LinkedNode<T>> {
+    fn from(tree: &ArenaTree<T>) -> Self {
+        tree.to_link()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::binary_tree::strategies::arb_tree;
+    use crate::common::binary_tree::{deep_clone, format_tree, parse_tree};
+    use crate::tree;
+    use proptest::{prop_assert_eq, proptest};
+
+    #[test]
+    fn navigation() {
+        let linked: NodeLink<i32> = tree![3, 5, 1, 6, 2, 0, 8, null, null, 7, 4];
+        let tree = ArenaTree::from_link(&linked);
+
+        let values: Vec<i32> = tree.preorder().into_iter().map(|id| tree[id]).collect();
+        assert_eq!(values, vec![3, 5, 6, 2, 7, 4, 1, 0, 8]);
+
+        let root = tree.root().unwrap();
+        let five = tree.left(root).unwrap();
+        let one = tree.right(root).unwrap();
+        let two = tree.right(five).unwrap();
+
+        assert_eq!(tree.parent(root), None);
+        assert_eq!(tree.parent(two), Some(five));
+        assert_eq!(tree.sibling(five), Some(one));
+        assert_eq!(tree.sibling(one), Some(five));
+        assert_eq!(tree.sibling(root), None);
+        assert_eq!(
+            tree.children(two).map(|id| tree[id]).collect::<Vec<_>>(),
+            vec![7, 4]
+        );
+        assert!(!tree.is_leaf(two));
+        assert!(tree.is_leaf(tree.left(two).unwrap()));
+    }
+
+    #[test]
+    fn sibling_of_only_child_is_none() {
+        let tree = ArenaTree::from_link(&tree![1, null, 2]);
+        let child = tree.right(tree.root().unwrap()).unwrap();
+
+        assert_eq!(tree.sibling(child), None);
+    }
+
+    #[test]
+    fn in_place_mutation() {
+        let mut tree = ArenaTree::with_root(4);
+        let root = tree.root().unwrap();
+        let two = tree.insert_left(root, 2);
+        let seven = tree.insert_right(root, 7);
+        tree.insert_left(two, 1);
+        tree.insert_right(two, 3);
+        tree.insert_left(seven, 6);
+        tree.insert_right(seven, 9);
+        assert_eq!(format_tree(&tree.to_link()), "[4,2,7,1,3,6,9]");
+
+        // invert the tree
+        for id in tree.preorder() {
+            tree.swap_children(id);
+        }
+        assert_eq!(format_tree(&tree.to_link()), "[4,7,2,9,6,3,1]");
+
+        tree[seven] = 70;
+        tree.detach(two);
+        assert_eq!(format_tree(&tree.to_link()), "[4,70,null,9,6]");
+
+        let old_right = tree.set_right(root, Some(two));
+        assert_eq!(old_right, None);
+        assert_eq!(tree.parent(two), Some(root));
+        assert_eq!(format_tree(&tree.to_link()), "[4,70,2,9,6,3,1]");
+
+        let old_left = tree.set_left(root, None);
+        assert_eq!(old_left, Some(seven));
+        assert_eq!(tree.parent(seven), None);
+
+        let old_root = tree.set_root(Some(seven));
+        assert_eq!(old_root, Some(root));
+        assert_eq!(format_tree(&tree.to_link()), "[70,9,6]");
+    }
+
+    #[test]
+    #[should_panic(expected = "node 1 is already attached")]
+    fn attaching_attached_node_panics() {
+        let mut tree = ArenaTree::from_link(&tree![1, 2, 3]);
+        let root = tree.root().unwrap();
+        let left = tree.left(root).unwrap();
+        let right = tree.right(root).unwrap();
+
+        tree.set_left(right, Some(left));
+    }
+
+    #[test]
+    #[should_panic(expected = "node 0 can't be attached under its descendant 2")]
+    fn attaching_node_under_its_descendant_panics() {
+        let mut tree = ArenaTree::new();
+        let top = tree.add_node(1);
+        let middle = tree.insert_left(top, 2);
+        let bottom = tree.insert_right(middle, 3);
+
+        tree.set_left(bottom, Some(top));
+    }
+
+    #[test]
+    #[should_panic(expected = "node 0 can't be attached under its descendant 0")]
+    fn attaching_node_under_itself_panics() {
+        let mut tree = ArenaTree::new();
+        let node = tree.add_node(1);
+
+        tree.set_right(node, Some(node));
+    }
+
+    #[test]
+    fn empty_tree() {
+        let tree = ArenaTree::<i32>::from_link(&None);
+        assert!(tree.is_empty());
+        assert_eq!(tree.root(), None);
+        assert_eq!(tree.to_link(), None);
+    }
+
+    #[test]
+    fn conversion_traits() {
+        let linked = parse_tree::<i32>("[1,null,2,3]").unwrap();
+        let tree = ArenaTree::from(&linked);
+        let converted: NodeLink<i32> = (&tree).into();
+
+        assert_eq!(converted, linked);
+    }
+
+    #[test]
+    fn deep_tree_conversion() {
+        let depth = 100_000;
+        let mut linked = None;
+        for val in (0..depth).rev() {
+            linked = TreeNode::new_link(val, None, linked);
+        }
+
+        let tree = ArenaTree::from_link(&linked);
+        assert_eq!(tree.len(), depth);
+        assert!(tree.to_link() == linked);
+    }
+
+    proptest! {
+        #[test]
+        fn conversion_is_lossless(linked in arb_tree(-100..100, 50, 10)) {
+            let tree = ArenaTree::from_link(&linked);
+            prop_assert_eq!(tree.to_link(), deep_clone(&linked));
+
+            for id in 0..tree.len() {
+                for child in tree.children(id) {
+                    prop_assert_eq!(tree.parent(child), Some(id));
+                }
+            }
+        }
+    }
+}
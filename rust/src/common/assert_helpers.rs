@@ -0,0 +1,488 @@
+//! Support code for the crate's assertion macros.
+//!
+//! The macros wrap compared values into [`AssertArg`] / [`AssertPair`] and call
+//! `assert_repr` / `assert_diff` on a reference to the wrapper. Method resolution
+//! prefers the impls for specific types (e.g. trees) and falls back to the
+//! autoref'd generic impls otherwise. Differences are looked up on `&&AssertPair`,
+//! so that matrices and trees go before sequences, and sequences before anything else.
+
+use std::fmt::{Debug, Display};
+use std::ops::Range;
+
+use crate::common::binary_tree::{render_tree, tree_diff, NodeLink};
+use crate::common::leetcode_format::ToLeetcode;
+use crate::common::linked_list::ListNode;
+
+/// value passed to an assertion macro
+pub struct AssertArg<'a, T: ?Sized>(pub &'a T);
+
+/// pair of values compared by an assertion macro
+pub struct AssertPair<'a, L: ?Sized, R: ?Sized>(pub &'a L, pub &'a R);
+
+/// representation of any value using its `Debug` implementation
+pub trait DebugRepr {
+    fn assert_repr(&self) -> String;
+}
+
+impl<T: Debug + ?Sized> DebugRepr for &AssertArg<'_, T> {
+    fn assert_repr(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+/// representation of a tree drawn in ascii
+pub trait TreeRepr {
+    fn assert_repr(&self) -> String;
+}
+
+impl<T: Display> TreeRepr for AssertArg<'_, NodeLink<T>> {
+    fn assert_repr(&self) -> String {
+        render_tree(self.0)
+    }
+}
+
+/// representation of a list with its values joined by arrows
+pub trait ListRepr {
+    fn assert_repr(&self) -> String;
+}
+
+impl<T: Display> ListRepr for AssertArg<'_, Option<Box<ListNode<T>>>> {
+    fn assert_repr(&self) -> String {
+        match self.0 {
+            Some(head) => head.to_string(),
+            None => "[]".to_string(),
+        }
+    }
+}
+
+/// values with a longer `Debug` representation are summarized, and their differences are explained
+const MAX_FULL_REPR_LEN: usize = 100;
+
+/// number of elements shown on each side of a difference
+const EXCERPT_RADIUS: usize = 3;
+
+fn is_long<T: Debug + ?Sized>(value: &T) -> bool {
+    format!("{:?}", value).len() > MAX_FULL_REPR_LEN
+}
+
+/// representation of a vector, cut short if it's long
+pub trait SeqRepr {
+    fn assert_repr(&self) -> String;
+}
+
+impl<T: Debug> SeqRepr for AssertArg<'_, Vec<T>> {
+    fn assert_repr(&self) -> String {
+        let mut repr = format!("{:?}", self.0);
+        if repr.len() > MAX_FULL_REPR_LEN {
+            let mut end = MAX_FULL_REPR_LEN;
+            while !repr.is_char_boundary(end) {
+                end -= 1;
+            }
+            repr.truncate(end);
+            repr += &format!("... ({} elements)", self.0.len());
+        }
+        repr
+    }
+}
+
+/// representation of a value the way the leetcode judge prints it
+pub trait LeetcodeRepr {
+    fn assert_repr(&self) -> String;
+}
+
+impl<T: ToLeetcode + ?Sized> LeetcodeRepr for AssertArg<'_, T> {
+    fn assert_repr(&self) -> String {
+        self.0.to_leetcode()
+    }
+}
+
+/// no explanation of a difference for values without a structure
+pub trait NoDiff {
+    fn assert_diff(&self) -> Option<String>;
+}
+
+impl<L: ?Sized, R: ?Sized> NoDiff for AssertPair<'_, L, R> {
+    fn assert_diff(&self) -> Option<String> {
+        None
+    }
+}
+
+/// first differing path of two trees
+pub trait TreeDiffRepr {
+    fn assert_diff(&self) -> Option<String>;
+}
+
+impl<T: PartialEq + Display> TreeDiffRepr for &AssertPair<'_, NodeLink<T>, NodeLink<T>> {
+    fn assert_diff(&self) -> Option<String> {
+        tree_diff(self.0, self.1).map(|diff| diff.to_string())
+    }
+}
+
+/// first differing index of two long vectors with the elements around it
+pub trait SeqDiffRepr {
+    fn assert_diff(&self) -> Option<String>;
+}
+
+impl<T: PartialEq<U> + Debug, U: Debug> SeqDiffRepr for &&AssertPair<'_, Vec<T>, Vec<U>> {
+    fn assert_diff(&self) -> Option<String> {
+        if !is_long(self.0) && !is_long(self.1) {
+            return None;
+        }
+
+        let index = first_difference(self.0, self.1)?;
+        Some(format!(
+            "index {} (lengths {} and {}){}{}",
+            index,
+            self.0.len(),
+            self.1.len(),
+            format_excerpt("left", self.0, index),
+            format_excerpt("right", self.1, index)
+        ))
+    }
+}
+
+/// first differing cell of two large matrices with the row around it
+pub trait MatrixDiffRepr {
+    fn assert_diff(&self) -> Option<String>;
+}
+
+impl<T: PartialEq<U> + Debug, U: Debug> MatrixDiffRepr
+    for &AssertPair<'_, Vec<Vec<T>>, Vec<Vec<U>>>
+{
+    fn assert_diff(&self) -> Option<String> {
+        if !is_long(self.0) && !is_long(self.1) {
+            return None;
+        }
+
+        let (left, right) = (self.0, self.1);
+        let row = first_difference(left, right)?;
+        if row >= left.len() || row >= right.len() {
+            // one of the matrices has no such row
+            return SeqDiffRepr::assert_diff(&&&AssertPair(left, right));
+        }
+
+        let column = first_difference(&left[row], &right[row])?;
+        Some(format!(
+            "row {}, column {} (row lengths {} and {}){}{}",
+            row,
+            column,
+            left[row].len(),
+            right[row].len(),
+            format_excerpt(&format!("left[{}]", row), &left[row], column),
+            format_excerpt(&format!("right[{}]", row), &right[row], column)
+        ))
+    }
+}
+
+/// index of the first difference of two sequences, which is the length of the shorter one
+/// if it's a prefix of the other one
+fn first_difference<T: PartialEq<U>, U>(left: &[T], right: &[U]) -> Option<usize> {
+    let common_len = left.len().min(right.len());
+
+    (0..common_len)
+        .find(|&index| left[index] != right[index])
+        .or((left.len() != right.len()).then_some(common_len))
+}
+
+/// range of elements shown around the index
+fn excerpt_range(len: usize, index: usize) -> Range<usize> {
+    let start = index.saturating_sub(EXCERPT_RADIUS).min(len);
+    let end = (index + EXCERPT_RADIUS + 1).min(len);
+    start..end
+}
+
+/// `name[start..end]: [.., a, b, c, ..]` line with the elements around the index
+fn format_excerpt<T: Debug>(name: &str, values: &[T], index: usize) -> String {
+    let range = excerpt_range(values.len(), index);
+
+    let mut items: Vec<String> = values[range.clone()]
+        .iter()
+        .map(|value| format!("{:?}", value))
+        .collect();
+    if range.start > 0 {
+        items.insert(0, "..".to_string());
+    }
+    if range.end < values.len() {
+        items.push("..".to_string());
+    }
+
+    let key = format!("{}[{}..{}]", name, range.start, range.end);
+    format_key_value(&key, &format!("[{}]", items.join(", ")))
+}
+
+/// elements of `left` without an equal element in `right` and vice versa.
+/// Every element is matched at most once, so repeated elements are counted
+pub fn unmatched<'a, L: PartialEq<R>, R>(
+    left: &'a [L],
+    right: &'a [R],
+) -> (Vec<&'a L>, Vec<&'a R>) {
+    let mut matched = vec![false; right.len()];
+
+    let mut left_only = vec![];
+    for left_value in left {
+        let position =
+            (0..right.len()).find(|&index| !matched[index] && *left_value == right[index]);
+        match position {
+            Some(index) => matched[index] = true,
+            None => left_only.push(left_value),
+        }
+    }
+
+    let right_only = right
+        .iter()
+        .zip(matched)
+        .filter(|(_, is_matched)| !is_matched)
+        .map(|(right_value, _)| right_value)
+        .collect();
+
+    (left_only, right_only)
+}
+
+/// collect nested collections into vectors, sorting every inner vector
+pub fn sort_inner<I, C, T>(values: I) -> Vec<Vec<T>>
+where
+    I: IntoIterator<Item = C>,
+    C: IntoIterator<Item = T>,
+    T: Ord,
+{
+    values
+        .into_iter()
+        .map(|inner| {
+            let mut inner: Vec<T> = inner.into_iter().collect();
+            inner.sort();
+            inner
+        })
+        .collect()
+}
+
+/// format a `key: value` line of an assertion message,
+/// putting a multiline value on separate indented lines
+pub fn format_key_value(key: &str, repr: &str) -> String {
+    if repr.contains('\n') {
+        let indented: Vec<String> = repr.lines().map(|line| format!("    {}", line)).collect();
+        format!("\n  {}:\n{}", key, indented.join("\n"))
+    } else {
+        format!("\n  {}: `{}`", key, repr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // `LeetcodeRepr` isn't imported, as it shares `assert_repr` with the other representations
+    use super::{
+        format_key_value, sort_inner, unmatched, AssertArg, AssertPair, DebugRepr, ListRepr,
+        MatrixDiffRepr, NoDiff, SeqDiffRepr, SeqRepr, TreeDiffRepr, TreeRepr,
+    };
+    use crate::common::binary_tree::parse_tree;
+    use crate::common::linked_list::vec_to_list;
+
+    #[test]
+    fn debug_repr_is_used_by_default() {
+        let value = (1, "a");
+        assert_eq!((&AssertArg(&value)).assert_repr(), "(1, \"a\")");
+    }
+
+    #[test]
+    fn tree_repr_is_used_for_trees() {
+        let tree = parse_tree::<i32>("[1,2]").unwrap();
+        assert_eq!(AssertArg(&tree).assert_repr(), "1\n|-- 2\n`-- null");
+    }
+
+    #[test]
+    fn list_repr_is_used_for_lists() {
+        let list = vec_to_list(vec![1, 4, 5]);
+        assert_eq!(AssertArg(&list).assert_repr(), "1 -> 4 -> 5");
+        assert_eq!(AssertArg(&vec_to_list::<i32>(vec![])).assert_repr(), "[]");
+    }
+
+    #[test]
+    fn diff_is_given_for_trees_only() {
+        let left = parse_tree::<i32>("[1,2]").unwrap();
+        let right = parse_tree::<i32>("[1,3]").unwrap();
+
+        assert_eq!(
+            (&&AssertPair(&left, &right)).assert_diff(),
+            Some("root.left: 2 != 3".to_string())
+        );
+        assert_eq!(AssertPair(&1, &2).assert_diff(), None);
+    }
+
+    #[test]
+    fn long_vectors_are_cut_short() {
+        let value = vec![1, 2, 3];
+        assert_eq!(AssertArg(&value).assert_repr(), "[1, 2, 3]");
+
+        let value: Vec<usize> = (0..1000).collect();
+        let repr = AssertArg(&value).assert_repr();
+        assert!(repr.starts_with("[0, 1, 2, "));
+        assert!(repr.ends_with("... (1000 elements)"));
+        assert!(repr.len() < 150);
+    }
+
+    #[test]
+    fn diff_is_given_for_long_vectors_only() {
+        assert_eq!((&&AssertPair(&vec![1, 2], &vec![1, 3])).assert_diff(), None);
+
+        let left: Vec<usize> = (0..1000).collect();
+        let mut right = left.clone();
+        right[500] = 0;
+        assert_eq!(
+            (&&AssertPair(&left, &right)).assert_diff().unwrap(),
+            "index 500 (lengths 1000 and 1000)\n  left[497..504]: `[.., 497, 498, 499, 500, 501, 502, 503, ..]`\n  right[497..504]: `[.., 497, 498, 499, 0, 501, 502, 503, ..]`"
+        );
+
+        right.truncate(500);
+        assert_eq!(
+            (&&AssertPair(&left, &right)).assert_diff().unwrap(),
+            "index 500 (lengths 1000 and 500)\n  left[497..504]: `[.., 497, 498, 499, 500, 501, 502, 503, ..]`\n  right[497..500]: `[.., 497, 498, 499]`"
+        );
+    }
+
+    #[test]
+    fn diff_is_given_for_large_matrices() {
+        let left: Vec<Vec<usize>> = (0..20)
+            .map(|row| (0..20).map(|col| row * col).collect())
+            .collect();
+
+        let mut right = left.clone();
+        right[7][1] = 0;
+        assert_eq!(
+            (&&AssertPair(&left, &right)).assert_diff().unwrap(),
+            "row 7, column 1 (row lengths 20 and 20)\n  left[7][0..5]: `[0, 7, 14, 21, 28, ..]`\n  right[7][0..5]: `[0, 0, 14, 21, 28, ..]`"
+        );
+
+        let mut right = left.clone();
+        right.push(vec![]);
+        assert_eq!(
+            (&&AssertPair(&left, &right)).assert_diff().unwrap(),
+            format!(
+                "index 20 (lengths 20 and 21)\n  left[17..20]: `[.., {:?}, {:?}, {:?}]`\n  right[17..21]: `[.., {:?}, {:?}, {:?}, []]`",
+                left[17], left[18], left[19], left[17], left[18], left[19]
+            )
+        );
+    }
+
+    #[test]
+    fn unmatched_counts_repeated_elements() {
+        let (left_only, right_only) = unmatched(&[1, 2, 2, 3], &[3, 2, 4]);
+        assert_eq!(left_only, vec![&1, &2]);
+        assert_eq!(right_only, vec![&4]);
+
+        let left = ["a".to_string()];
+        let (left_only, right_only) = unmatched(&left, &["a"]);
+        assert!(left_only.is_empty() && right_only.is_empty());
+    }
+
+    #[test]
+    fn inner_vectors_are_sorted() {
+        assert_eq!(
+            sort_inner(vec![vec![3, 1], vec![], vec![2, 1]]),
+            vec![vec![1, 3], vec![], vec![1, 2]]
+        );
+    }
+
+    #[test]
+    fn multiline_values_are_indented() {
+        assert_eq!(format_key_value("x", "1"), "\n  x: `1`");
+        assert_eq!(format_key_value("x", "1\n2"), "\n  x:\n    1\n    2");
+    }
+
+    #[test]
+    #[should_panic(expected = "where\n  left: `[0, 1, 2, ")]
+    fn crate_assert_eq_summarizes_long_vectors() {
+        let left: Vec<i32> = (0..10000).collect();
+        let right: Vec<i32> = (0..10000).rev().collect();
+
+        crate::assert_eq!(left, right);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "\ndifference: row 1, column 0 (row lengths 50 and 50)\n  left[1][0..4]:"
+    )]
+    fn crate_assert_eq_reports_matrix_difference() {
+        let left = vec![vec![0; 50]; 2];
+        let right = vec![vec![0; 50], vec![1; 50]];
+
+        crate::assert_eq!(left, right);
+    }
+
+    #[test]
+    fn crate_assert_eq_evaluates_arguments_once() {
+        let mut values = [1, 2, 3].into_iter();
+        crate::assert_eq!(values.next(), Some(1));
+        crate::assert_eq!(values.next(), Some(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "where\n  values.next(): `Some(1)`\n  Some(2): `Some(2)`")]
+    fn crate_assert_eq_reports_compared_values() {
+        let mut values = [1, 2, 3].into_iter();
+        crate::assert_eq!(values.next(), Some(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "difference: root.right: 3 != 4")]
+    fn crate_assert_eq_reports_tree_difference() {
+        let left = parse_tree::<i32>("[1,2,3]").unwrap();
+        let right = parse_tree::<i32>("[1,2,4]").unwrap();
+
+        crate::assert_eq!(left, right);
+    }
+
+    mod leetcode_repr_tests {
+        use crate::common::binary_tree::parse_tree;
+        use crate::common::linked_list::vec_to_list;
+
+        #[test]
+        fn leetcode_repr_is_preferred_to_debug() {
+            let value = vec![Some(1.5), None];
+            assert_eq!(crate::format_leetcode_repr!(value), "[1.50000,null]");
+
+            let list = vec_to_list(vec![1, 4, 5]);
+            assert_eq!(crate::format_leetcode_repr!(list), "[1,4,5]");
+
+            let tree = parse_tree::<String>("[a,null,b]").unwrap();
+            assert_eq!(crate::format_leetcode_repr!(tree), r#"["a",null,"b"]"#);
+
+            let value = std::time::Duration::from_secs(1);
+            assert_eq!(crate::format_leetcode_repr!(value), "1s");
+        }
+
+        #[test]
+        #[should_panic(
+            expected = "repeat([\"a\"], 2) returned [\"a\"]\nexpected result: [\"b\",\"c\"]"
+        )]
+        fn crate_assert_returns_prints_leetcode_values() {
+            fn repeat(words: [&'static str; 1], _: usize) -> Vec<&'static str> {
+                Vec::from(words)
+            }
+            crate::assert_returns!(vec!["b", "c"], repeat, ["a"], 2);
+        }
+
+        #[test]
+        fn crate_assert_eq_unordered_ignores_order() {
+            crate::assert_eq_unordered!(vec!["b".to_string(), "a".to_string()], vec!["a", "b"]);
+            crate::assert_eq_unordered!(
+                vec![vec![2, 1], vec![3]],
+                vec![vec![3], vec![1, 2]],
+                ignore_inner_order
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "\n  missing: `[[1,3]]`\n  unexpected: `[[3,1],[3,1]]`")]
+        fn crate_assert_eq_unordered_reports_missing_and_unexpected() {
+            crate::assert_eq_unordered!(
+                vec![vec![1, 2], vec![3, 1], vec![3, 1]],
+                vec![vec![1, 2], vec![1, 3]]
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "`[1, 1] == [1]` (ignoring order)\nwhere\n  unexpected: `[1]`")]
+        fn crate_assert_eq_unordered_reports_repeated_elements() {
+            crate::assert_eq_unordered!([1, 1], [1]);
+        }
+    }
+}
@@ -1,9 +1,12 @@
 use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fmt::Display;
 use std::rc::Rc;
+use std::str::FromStr;
 
 pub type NodeLink<T> = Option<Rc<RefCell<TreeNode<T>>>>;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug)]
 pub struct TreeNode<T> {
     pub val: T,
     pub left: NodeLink<T>,
@@ -25,3 +28,933 @@ impl<T> TreeNode<T> {
         Some(Rc::new(RefCell::new(TreeNode { val, left, right })))
     }
 }
+
+// Drop, Clone and PartialEq are implemented without recursion,
+// so that they work on degenerate trees with ~10^5 levels
+
+impl<T> Drop for TreeNode<T> {
+    fn drop(&mut self) {
+        let mut stack: Vec<Rc<RefCell<TreeNode<T>>>> = vec![];
+        stack.extend(self.left.take());
+        stack.extend(self.right.take());
+
+        while let Some(node) = stack.pop() {
+            // children of a node which is still referenced elsewhere must stay attached
+            if let Ok(cell) = Rc::try_unwrap(node) {
+                let mut node = cell.into_inner();
+                stack.extend(node.left.take());
+                stack.extend(node.right.take());
+            }
+        }
+    }
+}
+
+impl<T: Clone> Clone for TreeNode<T> {
+    /// deep copy of the node and its whole subtree
+    fn clone(&self) -> Self {
+        TreeNode {
+            val: self.val.clone(),
+            left: deep_clone(&self.left),
+            right: deep_clone(&self.right),
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for TreeNode<T> {
+    fn eq(&self, other: &Self) -> bool {
+        if self.val != other.val {
+            return false;
+        }
+
+        let mut stack = vec![
+            (self.left.clone(), other.left.clone()),
+            (self.right.clone(), other.right.clone()),
+        ];
+
+        while let Some(pair) = stack.pop() {
+            match pair {
+                (None, None) => {}
+                (Some(left_node), Some(right_node)) => {
+                    let (left_ref, right_ref) = (left_node.borrow(), right_node.borrow());
+                    if left_ref.val != right_ref.val {
+                        return false;
+                    }
+
+                    stack.push((left_ref.left.clone(), right_ref.left.clone()));
+                    stack.push((left_ref.right.clone(), right_ref.right.clone()));
+                }
+                _ => return false,
+            }
+        }
+
+        true
+    }
+}
+
+impl<T: Eq> Eq for TreeNode<T> {}
+
+/// copy a whole tree, so that the copy shares no nodes with the original
+/// (unlike `NodeLink::clone` which only copies the pointer to the root)
+pub fn deep_clone<T: Clone>(root: &NodeLink<T>) -> NodeLink<T> {
+    let copy_node = |node: &Rc<RefCell<TreeNode<T>>>| {
+        Rc::new(RefCell::new(TreeNode::new(node.borrow().val.clone())))
+    };
+
+    let root = root.as_ref()?;
+    let root_copy = copy_node(root);
+
+    let mut stack = vec![(root.clone(), root_copy.clone())];
+    while let Some((original, copy)) = stack.pop() {
+        let original = original.borrow();
+        let mut copy = copy.borrow_mut();
+
+        if let Some(left) = &original.left {
+            let left_copy = copy_node(left);
+            copy.left = Some(left_copy.clone());
+            stack.push((left.clone(), left_copy));
+        }
+        if let Some(right) = &original.right {
+            let right_copy = copy_node(right);
+            copy.right = Some(right_copy.clone());
+            stack.push((right.clone(), right_copy));
+        }
+    }
+
+    Some(root_copy)
+}
+
+/// error which can be returned when parsing a tree from leetcode format
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParseTreeError {
+    /// input isn't enclosed in square brackets
+    MissingBrackets,
+    /// token at a given index is neither `null` nor a valid node value
+    InvalidValue { index: usize, token: String },
+    /// token at a given index is a value which has no parent node to attach to
+    DanglingValue { index: usize, token: String },
+}
+
+impl Display for ParseTreeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseTreeError::MissingBrackets => {
+                write!(f, "tree must be enclosed in square brackets")
+            }
+            ParseTreeError::InvalidValue { index, token } => {
+                write!(f, "invalid node value `{}` at index {}", token, index)
+            }
+            ParseTreeError::DanglingValue { index, token } => {
+                write!(f, "value `{}` at index {} has no parent node", token, index)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseTreeError {}
+
+/// parse a tree from leetcode level-order format, e.g. `[1,null,2,3]`
+pub fn parse_tree<T: FromStr>(input: &str) -> Result<NodeLink<T>, ParseTreeError> {
+    let inner = input
+        .trim()
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .ok_or(ParseTreeError::MissingBrackets)?
+        .trim();
+
+    if inner.is_empty() {
+        return Ok(None);
+    }
+
+    let tokens: Vec<&str> = inner.split(',').map(str::trim).collect();
+
+    let mut values = vec![];
+    for (index, &token) in tokens.iter().enumerate() {
+        let value = match token {
+            "null" => None,
+            _ => Some(
+                token
+                    .parse::<T>()
+                    .map_err(|_| ParseTreeError::InvalidValue {
+                        index,
+                        token: token.to_owned(),
+                    })?,
+            ),
+        };
+        values.push(value);
+    }
+
+    build_level_order(values).map_err(|index| ParseTreeError::DanglingValue {
+        index,
+        token: tokens[index].to_owned(),
+    })
+}
+
+/// build a tree from values in level-order, where `None` stands for a missing node.
+/// Panics if some value has no parent node to attach to
+pub fn from_level_order<T>(values: Vec<Option<T>>) -> NodeLink<T> {
+    build_level_order(values)
+        .unwrap_or_else(|index| panic!("value at index {} has no parent node", index))
+}
+
+/// build a tree from values in level-order or return the index of a value which has no parent
+fn build_level_order<T>(values: Vec<Option<T>>) -> Result<NodeLink<T>, usize> {
+    let mut values = values.into_iter().enumerate();
+    let root = match values.next() {
+        Some((_, Some(val))) => TreeNode::new_link(val, None, None),
+        _ => None,
+    };
+
+    // nodes waiting for their children, paired with a flag telling if the left one is assigned
+    let mut parents: VecDeque<(Rc<RefCell<TreeNode<T>>>, bool)> = VecDeque::new();
+    parents.extend(root.clone().map(|node| (node, false)));
+
+    for (index, value) in values {
+        let Some((parent, left_assigned)) = parents.pop_front() else {
+            if value.is_some() {
+                return Err(index);
+            }
+            continue;
+        };
+
+        let child = value.and_then(|val| TreeNode::new_link(val, None, None));
+        if let Some(child_node) = child.clone() {
+            parents.push_back((child_node, false));
+        }
+
+        if left_assigned {
+            parent.borrow_mut().right = child;
+        } else {
+            parent.borrow_mut().left = child;
+            parents.push_front((parent, true));
+        }
+    }
+
+    Ok(root)
+}
+
+/// placeholder for a missing node in the `tree!` macro literals
+pub struct Null;
+
+/// slot of the `tree!` macro literal holding either a node value or `Null`
+pub struct TreeSlot<T>(pub Option<T>);
+
+/// conversion of a `Null` slot into a missing node.
+/// Takes precedence over `ValueSlot` thanks to autoref-based method resolution
+pub trait NullSlot {
+    fn take_slot<U>(&mut self) -> Option<U>;
+}
+
+impl NullSlot for TreeSlot<Null> {
+    fn take_slot<U>(&mut self) -> Option<U> {
+        None
+    }
+}
+
+/// conversion of a slot holding a value into a node value
+pub trait ValueSlot<T> {
+    fn take_slot(&mut self) -> Option<T>;
+}
+
+impl<T> ValueSlot<T> for &mut TreeSlot<T> {
+    fn take_slot(&mut self) -> Option<T> {
+        self.0.take()
+    }
+}
+
+/// format a tree in leetcode level-order format with trailing nulls trimmed, e.g. `[1,null,2,3]`
+pub fn format_tree<T: Display>(root: &NodeLink<T>) -> String {
+    format_tree_with(root, T::to_string)
+}
+
+/// format a tree in leetcode level-order format, formatting values with the given function
+pub fn format_tree_with<T>(
+    root: &NodeLink<T>,
+    mut format_value: impl FnMut(&T) -> String,
+) -> String {
+    let mut tokens: Vec<Option<String>> = vec![];
+
+    let mut queue = VecDeque::new();
+    queue.push_back(root.clone());
+
+    while let Some(link) = queue.pop_front() {
+        match link {
+            Some(node) => {
+                let node = node.borrow();
+                tokens.push(Some(format_value(&node.val)));
+                queue.push_back(node.left.clone());
+                queue.push_back(node.right.clone());
+            }
+            None => tokens.push(None),
+        }
+    }
+
+    while let Some(None) = tokens.last() {
+        tokens.pop();
+    }
+
+    let mut result = String::from("[");
+    for (idx, token) in tokens.iter().enumerate() {
+        if idx > 0 {
+            result.push(',');
+        }
+        match token {
+            Some(value) => result.push_str(value),
+            None => result.push_str("null"),
+        }
+    }
+    result.push(']');
+
+    result
+}
+
+/// iterator over tree nodes in pre-order (node, left, right)
+pub struct PreorderIter<T> {
+    stack: Vec<Rc<RefCell<TreeNode<T>>>>,
+}
+
+impl<T> Iterator for PreorderIter<T> {
+    type Item = Rc<RefCell<TreeNode<T>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        {
+            let node_ref = node.borrow();
+            self.stack.extend(node_ref.right.clone());
+            self.stack.extend(node_ref.left.clone());
+        }
+        Some(node)
+    }
+}
+
+/// iterator over tree nodes in in-order (left, node, right)
+pub struct InorderIter<T> {
+    stack: Vec<Rc<RefCell<TreeNode<T>>>>,
+    current: NodeLink<T>,
+}
+
+impl<T> Iterator for InorderIter<T> {
+    type Item = Rc<RefCell<TreeNode<T>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node) = self.current.take() {
+            self.current = node.borrow().left.clone();
+            self.stack.push(node);
+        }
+
+        let node = self.stack.pop()?;
+        self.current = node.borrow().right.clone();
+        Some(node)
+    }
+}
+
+/// iterator over tree nodes in post-order (left, right, node)
+pub struct PostorderIter<T> {
+    /// nodes paired with a flag telling if their children are already on the stack
+    stack: Vec<(Rc<RefCell<TreeNode<T>>>, bool)>,
+}
+
+impl<T> Iterator for PostorderIter<T> {
+    type Item = Rc<RefCell<TreeNode<T>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (node, expanded) = self.stack.pop()?;
+            if expanded {
+                return Some(node);
+            }
+
+            let (left, right) = {
+                let node_ref = node.borrow();
+                (node_ref.left.clone(), node_ref.right.clone())
+            };
+            self.stack.push((node, true));
+            self.stack.extend(right.map(|child| (child, false)));
+            self.stack.extend(left.map(|child| (child, false)));
+        }
+    }
+}
+
+/// iterator over tree nodes in level-order paired with their depths (root has depth 0)
+pub struct LevelOrderIter<T> {
+    queue: VecDeque<(usize, Rc<RefCell<TreeNode<T>>>)>,
+}
+
+impl<T> Iterator for LevelOrderIter<T> {
+    type Item = (usize, Rc<RefCell<TreeNode<T>>>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (depth, node) = self.queue.pop_front()?;
+        {
+            let node_ref = node.borrow();
+            self.queue
+                .extend(node_ref.left.clone().map(|child| (depth + 1, child)));
+            self.queue
+                .extend(node_ref.right.clone().map(|child| (depth + 1, child)));
+        }
+        Some((depth, node))
+    }
+}
+
+/// iterate over the tree in pre-order
+pub fn preorder<T>(root: &NodeLink<T>) -> PreorderIter<T> {
+    PreorderIter {
+        stack: root.iter().cloned().collect(),
+    }
+}
+
+/// iterate over the tree in in-order
+pub fn inorder<T>(root: &NodeLink<T>) -> InorderIter<T> {
+    InorderIter {
+        stack: vec![],
+        current: root.clone(),
+    }
+}
+
+/// iterate over the tree in post-order
+pub fn postorder<T>(root: &NodeLink<T>) -> PostorderIter<T> {
+    PostorderIter {
+        stack: root.iter().map(|node| (node.clone(), false)).collect(),
+    }
+}
+
+/// iterate over the tree in level-order, yielding a depth of each node along with it
+pub fn level_order<T>(root: &NodeLink<T>) -> LevelOrderIter<T> {
+    LevelOrderIter {
+        queue: root.iter().map(|node| (0, node.clone())).collect(),
+    }
+}
+
+/// draw a tree in ascii, one node per line with children indented below their parent.
+/// A missing child of a node which has the other one is drawn as `null`
+pub fn render_tree<T: Display>(root: &NodeLink<T>) -> String {
+    let mut result = String::new();
+
+    // (node, prefix of its own line, prefix of its children's lines)
+    let mut stack = vec![(root.clone(), String::new(), String::new())];
+
+    while let Some((link, line_prefix, children_prefix)) = stack.pop() {
+        result.push_str(&line_prefix);
+
+        let Some(node) = link else {
+            result.push_str("null\n");
+            continue;
+        };
+        let node = node.borrow();
+        result.push_str(&format!("{}\n", node.val));
+
+        if node.left.is_none() && node.right.is_none() {
+            continue;
+        }
+
+        stack.push((
+            node.right.clone(),
+            format!("{}`-- ", children_prefix),
+            format!("{}    ", children_prefix),
+        ));
+        stack.push((
+            node.left.clone(),
+            format!("{}|-- ", children_prefix),
+            format!("{}|   ", children_prefix),
+        ));
+    }
+
+    result.pop();
+    result
+}
+
+/// first difference between two trees found in pre-order
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TreeDiff {
+    /// path to the differing node, e.g. `root.left.right`
+    pub path: String,
+    /// value of the node in the left tree or `null`
+    pub left: String,
+    /// value of the node in the right tree or `null`
+    pub right: String,
+}
+
+impl Display for TreeDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} != {}", self.path, self.left, self.right)
+    }
+}
+
+/// find the first (in pre-order) node where two trees differ either in value or in structure
+pub fn tree_diff<T: PartialEq + Display>(
+    left: &NodeLink<T>,
+    right: &NodeLink<T>,
+) -> Option<TreeDiff> {
+    let node_repr = |node: &Rc<RefCell<TreeNode<T>>>| node.borrow().val.to_string();
+
+    let mut stack = vec![(left.clone(), right.clone(), String::from("root"))];
+
+    while let Some((left_link, right_link, path)) = stack.pop() {
+        match (left_link, right_link) {
+            (None, None) => {}
+            (Some(left_node), Some(right_node)) => {
+                if Rc::ptr_eq(&left_node, &right_node) {
+                    continue;
+                }
+
+                let (left_ref, right_ref) = (left_node.borrow(), right_node.borrow());
+                if left_ref.val != right_ref.val {
+                    return Some(TreeDiff {
+                        path,
+                        left: left_ref.val.to_string(),
+                        right: right_ref.val.to_string(),
+                    });
+                }
+
+                stack.push((
+                    left_ref.right.clone(),
+                    right_ref.right.clone(),
+                    format!("{}.right", path),
+                ));
+                stack.push((
+                    left_ref.left.clone(),
+                    right_ref.left.clone(),
+                    format!("{}.left", path),
+                ));
+            }
+            (left_link, right_link) => {
+                return Some(TreeDiff {
+                    path,
+                    left: left_link.as_ref().map_or("null".into(), node_repr),
+                    right: right_link.as_ref().map_or("null".into(), node_repr),
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// proptest strategies generating random trees
+#[cfg(test)]
+pub mod strategies {
+    use super::*;
+    use proptest::collection::{btree_set, vec};
+    use proptest::prelude::*;
+    use proptest::sample::Index;
+    use std::fmt::Debug;
+
+    /// attach nodes one by one to free child slots chosen by `slots`
+    /// (the first value becomes the root), stopping when there are no slots left within `max_depth`
+    fn attach_nodes<T>(values: Vec<T>, slots: &[Index], max_depth: usize) -> NodeLink<T> {
+        let mut values = values.into_iter();
+        let root = TreeNode::new_link(values.next()?, None, None);
+
+        // free child slots as (parent, is_left_child, parent_depth)
+        let mut free_slots = vec![];
+        if max_depth > 0 {
+            let root_node = root.clone().unwrap();
+            free_slots.push((root_node.clone(), true, 0));
+            free_slots.push((root_node, false, 0));
+        }
+
+        for (val, slot) in values.zip(slots) {
+            if free_slots.is_empty() {
+                break;
+            }
+
+            let (parent, is_left, parent_depth) =
+                free_slots.swap_remove(slot.index(free_slots.len()));
+            let child = TreeNode::new_link(val, None, None);
+
+            if parent_depth + 1 < max_depth {
+                let child_node = child.clone().unwrap();
+                free_slots.push((child_node.clone(), true, parent_depth + 1));
+                free_slots.push((child_node, false, parent_depth + 1));
+            }
+
+            if is_left {
+                parent.borrow_mut().left = child;
+            } else {
+                parent.borrow_mut().right = child;
+            }
+        }
+
+        root
+    }
+
+    /// trees of arbitrary shape with at most `max_nodes` nodes and no node deeper than `max_depth`
+    /// (the root has depth 0). Shrinks towards trees with fewer nodes and smaller values
+    pub fn arb_tree<S>(
+        values: S,
+        max_nodes: usize,
+        max_depth: usize,
+    ) -> impl Strategy<Value = NodeLink<S::Value>>
+    where
+        S: Strategy,
+        S::Value: Debug,
+    {
+        vec((values, any::<Index>()), 0..=max_nodes).prop_map(move |nodes| {
+            let (values, slots): (Vec<_>, Vec<_>) = nodes.into_iter().unzip();
+            attach_nodes(values, &slots[1.min(slots.len())..], max_depth)
+        })
+    }
+
+    /// valid binary search trees with distinct values, at most `max_nodes` nodes
+    /// and no node deeper than `max_depth` (the root has depth 0)
+    pub fn arb_bst<S>(
+        values: S,
+        max_nodes: usize,
+        max_depth: usize,
+    ) -> impl Strategy<Value = NodeLink<S::Value>>
+    where
+        S: Strategy,
+        S::Value: Ord + Clone + Debug,
+    {
+        (
+            btree_set(values, 0..=max_nodes),
+            vec(any::<Index>(), max_nodes),
+        )
+            .prop_map(move |(values, slots)| {
+                let tree = attach_nodes(values.iter().cloned().collect(), &slots, max_depth);
+
+                for (node, val) in inorder(&tree).zip(values) {
+                    node.borrow_mut().val = val;
+                }
+
+                tree
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn leaf(val: i32) -> NodeLink<i32> {
+        TreeNode::new_link(val, None, None)
+    }
+
+    #[test]
+    fn parse_empty() {
+        assert_eq!(parse_tree::<i32>("[]"), Ok(None));
+        assert_eq!(parse_tree::<i32>(" [ ] "), Ok(None));
+        assert_eq!(parse_tree::<i32>("[null]"), Ok(None));
+    }
+
+    #[test]
+    fn parse_right_skewed() {
+        let expected = TreeNode::new_link(1, None, TreeNode::new_link(2, leaf(3), None));
+        assert_eq!(parse_tree("[1,null,2,3]"), Ok(expected));
+    }
+
+    #[test]
+    fn parse_complete() {
+        let expected = TreeNode::new_link(
+            -1,
+            TreeNode::new_link(20, leaf(4), leaf(5)),
+            TreeNode::new_link(3, leaf(6), None),
+        );
+        assert_eq!(parse_tree("[-1, 20, 3, 4, 5, 6]"), Ok(expected));
+    }
+
+    #[test]
+    fn parse_ignores_trailing_nulls() {
+        let expected = TreeNode::new_link(1, leaf(2), None);
+        assert_eq!(parse_tree("[1,2,null,null,null,null,null]"), Ok(expected));
+    }
+
+    #[test]
+    fn parse_non_integer_values() {
+        let expected = TreeNode::new_link(
+            "a".to_string(),
+            None,
+            TreeNode::new_link("b".to_string(), None, None),
+        );
+        assert_eq!(parse_tree("[a,null,b]"), Ok(expected));
+    }
+
+    #[rstest]
+    #[case("1,2,3")]
+    #[case("[1,2,3")]
+    #[case("1,2,3]")]
+    #[case("")]
+    fn parse_fails_without_brackets(#[case] input: &str) {
+        assert_eq!(
+            parse_tree::<i32>(input),
+            Err(ParseTreeError::MissingBrackets)
+        );
+    }
+
+    #[rstest]
+    #[case("[1,x,3]", 1, "x")]
+    #[case("[1,2,,3]", 2, "")]
+    #[case("[1,2,3.5]", 2, "3.5")]
+    #[case("[nil]", 0, "nil")]
+    fn parse_fails_on_invalid_value(
+        #[case] input: &str,
+        #[case] index: usize,
+        #[case] token: &str,
+    ) {
+        assert_eq!(
+            parse_tree::<i32>(input),
+            Err(ParseTreeError::InvalidValue {
+                index,
+                token: token.to_owned()
+            })
+        );
+    }
+
+    #[rstest]
+    #[case("[null,1]", 1, "1")]
+    #[case("[1,null,null,2]", 3, "2")]
+    #[case("[1,2,null,null,null,3]", 5, "3")]
+    fn parse_fails_on_dangling_value(
+        #[case] input: &str,
+        #[case] index: usize,
+        #[case] token: &str,
+    ) {
+        assert_eq!(
+            parse_tree::<i32>(input),
+            Err(ParseTreeError::DanglingValue {
+                index,
+                token: token.to_owned()
+            })
+        );
+    }
+
+    #[rstest]
+    #[case("[]")]
+    #[case("[1]")]
+    #[case("[1,null,2,3]")]
+    #[case("[1,2,3,4,5,null,6,7,null,null,null,null,8]")]
+    #[case("[5,4,8,11,null,13,4,7,2,null,null,5,1]")]
+    #[case("[1,null,2,null,3,null,4]")]
+    fn format_parsed(#[case] input: &str) {
+        let tree = parse_tree::<i32>(input).unwrap();
+        assert_eq!(format_tree(&tree), input);
+    }
+
+    #[test]
+    fn format_trims_trailing_nulls() {
+        let tree = TreeNode::new_link(1, leaf(2), None);
+        assert_eq!(format_tree(&tree), "[1,2]");
+    }
+
+    fn values<T: Clone>(nodes: impl Iterator<Item = Rc<RefCell<TreeNode<T>>>>) -> Vec<T> {
+        nodes.map(|node| node.borrow().val.clone()).collect()
+    }
+
+    /// build a tree where every node has only a left child (or only a right one)
+    fn degenerate_tree(depth: usize, to_the_left: bool) -> NodeLink<usize> {
+        let mut root = None;
+        for val in (0..depth).rev() {
+            root = if to_the_left {
+                TreeNode::new_link(val, root, None)
+            } else {
+                TreeNode::new_link(val, None, root)
+            };
+        }
+        root
+    }
+
+    #[rstest]
+    #[case("[]", vec![], vec![], vec![])]
+    #[case("[1]", vec![1], vec![1], vec![1])]
+    #[case("[1,null,2,3]", vec![1, 2, 3], vec![1, 3, 2], vec![3, 2, 1])]
+    #[case(
+        "[1,2,3,4,5,null,6,7,null,null,null,null,8]",
+        vec![1, 2, 4, 7, 5, 3, 6, 8],
+        vec![7, 4, 2, 5, 1, 3, 6, 8],
+        vec![7, 4, 5, 2, 8, 6, 3, 1]
+    )]
+    fn depth_first_traversals(
+        #[case] input: &str,
+        #[case] expected_preorder: Vec<i32>,
+        #[case] expected_inorder: Vec<i32>,
+        #[case] expected_postorder: Vec<i32>,
+    ) {
+        let tree = parse_tree::<i32>(input).unwrap();
+
+        assert_eq!(values(preorder(&tree)), expected_preorder);
+        assert_eq!(values(inorder(&tree)), expected_inorder);
+        assert_eq!(values(postorder(&tree)), expected_postorder);
+    }
+
+    #[test]
+    fn level_order_yields_depths() {
+        let tree = parse_tree::<i32>("[1,2,3,4,5,null,6,7,null,null,null,null,8]").unwrap();
+
+        let levels: Vec<(usize, i32)> = level_order(&tree)
+            .map(|(depth, node)| (depth, node.borrow().val))
+            .collect();
+
+        assert_eq!(
+            levels,
+            vec![
+                (0, 1),
+                (1, 2),
+                (1, 3),
+                (2, 4),
+                (2, 5),
+                (2, 6),
+                (3, 7),
+                (3, 8)
+            ]
+        );
+    }
+
+    #[rstest]
+    fn traversals_dont_overflow_on_deep_trees(#[values(true, false)] to_the_left: bool) {
+        let depth = 100_000;
+        let tree = degenerate_tree(depth, to_the_left);
+
+        let expected_preorder: Vec<usize> = (0..depth).collect();
+        let expected_postorder: Vec<usize> = (0..depth).rev().collect();
+        let expected_inorder = if to_the_left {
+            expected_postorder.clone()
+        } else {
+            expected_preorder.clone()
+        };
+
+        assert_eq!(values(preorder(&tree)), expected_preorder);
+        assert_eq!(values(inorder(&tree)), expected_inorder);
+        assert_eq!(values(postorder(&tree)), expected_postorder);
+        assert!(level_order(&tree).all(|(depth, node)| depth == node.borrow().val));
+    }
+
+    #[test]
+    fn render_empty() {
+        assert_eq!(render_tree::<i32>(&None), "null");
+    }
+
+    #[test]
+    fn render_shows_missing_siblings_as_null() {
+        let tree = parse_tree::<i32>("[1,2,3,4,5,null,6,7,null,null,null,null,8]").unwrap();
+
+        let expected = [
+            "1",
+            "|-- 2",
+            "|   |-- 4",
+            "|   |   |-- 7",
+            "|   |   `-- null",
+            "|   `-- 5",
+            "`-- 3",
+            "    |-- null",
+            "    `-- 6",
+            "        |-- null",
+            "        `-- 8",
+        ]
+        .join("\n");
+
+        assert_eq!(render_tree(&tree), expected);
+    }
+
+    #[rstest]
+    #[case("[]", "[]")]
+    #[case("[1,2,3]", "[1,2,3]")]
+    #[case("[1,null,2,3]", "[1,null,2,3]")]
+    fn diff_of_equal_trees_is_empty(#[case] left: &str, #[case] right: &str) {
+        let left = parse_tree::<i32>(left).unwrap();
+        let right = parse_tree::<i32>(right).unwrap();
+
+        assert_eq!(tree_diff(&left, &right), None);
+    }
+
+    #[rstest]
+    #[case("[1,2,3]", "[4,2,3]", "root: 1 != 4")]
+    #[case("[1,2,3,null,3]", "[1,2,3,null,4]", "root.left.right: 3 != 4")]
+    #[case("[1,2,3,null,3]", "[1,2,5,null,4]", "root.left.right: 3 != 4")]
+    #[case("[1,2]", "[1,null,2]", "root.left: 2 != null")]
+    #[case("[]", "[1]", "root: null != 1")]
+    #[case("[1,2,3]", "[1,2,3,null,null,4]", "root.right.left: null != 4")]
+    fn diff_names_first_differing_path(
+        #[case] left: &str,
+        #[case] right: &str,
+        #[case] expected: &str,
+    ) {
+        let left = parse_tree::<i32>(left).unwrap();
+        let right = parse_tree::<i32>(right).unwrap();
+
+        let diff = tree_diff(&left, &right).unwrap();
+        assert_eq!(diff.to_string(), expected);
+    }
+
+    #[test]
+    fn deep_trees_are_dropped_cloned_and_compared() {
+        let depth = 1_000_000;
+        let tree = degenerate_tree(depth, true);
+
+        let copy = deep_clone(&tree);
+        assert!(copy == tree);
+
+        let other = degenerate_tree(depth, false);
+        assert!(other != tree);
+
+        let last_node = preorder(&copy).last().unwrap();
+        last_node.borrow_mut().val += 1;
+        assert!(copy != tree);
+    }
+
+    #[test]
+    fn cloned_node_owns_its_subtree() {
+        let tree = parse_tree::<i32>("[1,2,3]").unwrap();
+        let node_copy = tree.as_ref().unwrap().borrow().clone();
+
+        node_copy.left.as_ref().unwrap().borrow_mut().val = 5;
+
+        assert_eq!(format_tree(&tree), "[1,2,3]");
+        assert_eq!(node_copy.left.as_ref().unwrap().borrow().val, 5);
+    }
+
+    #[test]
+    fn dropping_tree_keeps_shared_subtrees() {
+        let subtree = parse_tree::<i32>("[2,3,4]").unwrap();
+        let tree = TreeNode::new_link(1, subtree.clone(), None);
+
+        drop(tree);
+
+        assert_eq!(format_tree(&subtree), "[2,3,4]");
+    }
+
+    mod strategies_tests {
+        use super::super::strategies::{arb_bst, arb_tree};
+        use super::super::*;
+        use proptest::{prop_assert, prop_assert_eq, proptest};
+
+        proptest! {
+            #[test]
+            fn parse_format(tree in arb_tree(-100..100, 30, 6)) {
+                let formatted = format_tree(&tree);
+                let parsed = parse_tree::<i32>(&formatted).unwrap();
+
+                prop_assert_eq!(parsed, tree);
+            }
+
+            #[test]
+            fn deep_clone_shares_no_nodes(tree in arb_tree(-100..100, 30, 6)) {
+                let copy = deep_clone(&tree);
+                prop_assert_eq!(&copy, &tree);
+
+                for (original, copied) in preorder(&tree).zip(preorder(&copy)) {
+                    prop_assert!(!Rc::ptr_eq(&original, &copied));
+                }
+            }
+
+            #[test]
+            fn arb_tree_respects_bounds(tree in arb_tree(0..10, 20, 4)) {
+                prop_assert!(preorder(&tree).count() <= 20);
+                prop_assert!(level_order(&tree).all(|(depth, _)| depth <= 4));
+            }
+
+            #[test]
+            fn arb_tree_fits_max_depth(tree in arb_tree(0..10, 20, 0)) {
+                prop_assert!(preorder(&tree).count() <= 1);
+            }
+
+            #[test]
+            fn arb_bst_is_valid(tree in arb_bst(-1000..1000, 50, 5)) {
+                let values: Vec<i32> = inorder(&tree).map(|node| node.borrow().val).collect();
+
+                prop_assert!(values.len() <= 50);
+                prop_assert!(values.windows(2).all(|pair| pair[0] < pair[1]));
+                prop_assert!(level_order(&tree).all(|(depth, _)| depth <= 5));
+            }
+        }
+    }
+}
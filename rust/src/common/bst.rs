@@ -0,0 +1,323 @@
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::rc::Rc;
+
+use crate::common::binary_tree::{inorder, NodeLink, TreeNode};
+
+/// insert the value into the tree if it isn't present.
+/// Returns whether the value was inserted
+pub fn insert<T: Ord>(root: &mut NodeLink<T>, val: T) -> bool {
+    let Some(mut node) = root.clone() else {
+        *root = TreeNode::new_link(val, None, None);
+        return true;
+    };
+
+    loop {
+        let next = {
+            let mut node_ref = node.borrow_mut();
+            let child = match val.cmp(&node_ref.val) {
+                Ordering::Equal => return false,
+                Ordering::Less => &mut node_ref.left,
+                Ordering::Greater => &mut node_ref.right,
+            };
+
+            match child {
+                Some(child_node) => child_node.clone(),
+                None => {
+                    *child = TreeNode::new_link(val, None, None);
+                    return true;
+                }
+            }
+        };
+        node = next;
+    }
+}
+
+/// remove the value from the tree by relinking its nodes.
+/// Returns whether the value was present
+pub fn delete<T: Ord>(root: &mut NodeLink<T>, val: &T) -> bool {
+    // parent of the current node paired with a flag telling if the current node is its left child
+    let mut parent: Option<(Rc<RefCell<TreeNode<T>>>, bool)> = None;
+    let mut current = root.clone();
+
+    while let Some(node) = current.clone() {
+        let node_ref = node.borrow();
+        match val.cmp(&node_ref.val) {
+            Ordering::Equal => break,
+            Ordering::Less => {
+                current = node_ref.left.clone();
+                parent = Some((node.clone(), true));
+            }
+            Ordering::Greater => {
+                current = node_ref.right.clone();
+                parent = Some((node.clone(), false));
+            }
+        }
+    }
+
+    let Some(node) = current else {
+        return false;
+    };
+    let replacement = detach(&node);
+
+    match parent {
+        None => *root = replacement,
+        Some((parent, true)) => parent.borrow_mut().left = replacement,
+        Some((parent, false)) => parent.borrow_mut().right = replacement,
+    }
+
+    true
+}
+
+/// unlink children of the node and join them into a single subtree which can replace it
+fn detach<T>(node: &Rc<RefCell<TreeNode<T>>>) -> NodeLink<T> {
+    let (left, right) = {
+        let mut node_ref = node.borrow_mut();
+        (node_ref.left.take(), node_ref.right.take())
+    };
+
+    let (Some(left), Some(right)) = (left.clone(), right.clone()) else {
+        return left.or(right);
+    };
+
+    // the successor is the leftmost node of the right subtree
+    let right_left = right.borrow().left.clone();
+    let mut successor_parent = right.clone();
+    let mut successor = match right_left {
+        Some(successor) => successor,
+        None => {
+            right.borrow_mut().left = Some(left);
+            return Some(right);
+        }
+    };
+
+    loop {
+        let next = successor.borrow().left.clone();
+        let Some(next) = next else {
+            break;
+        };
+        successor_parent = successor;
+        successor = next;
+    }
+
+    {
+        let mut successor_ref = successor.borrow_mut();
+        successor_parent.borrow_mut().left = successor_ref.right.take();
+        successor_ref.left = Some(left);
+        successor_ref.right = Some(right);
+    }
+
+    Some(successor)
+}
+
+/// find the node with a given value
+pub fn search<T: Ord>(root: &NodeLink<T>, val: &T) -> NodeLink<T> {
+    let mut current = root.clone();
+
+    while let Some(node) = current {
+        let node_ref = node.borrow();
+        current = match val.cmp(&node_ref.val) {
+            Ordering::Equal => return Some(node.clone()),
+            Ordering::Less => node_ref.left.clone(),
+            Ordering::Greater => node_ref.right.clone(),
+        };
+    }
+
+    None
+}
+
+/// find the node with the smallest value which is not less than a given one
+pub fn lower_bound<T: Ord>(root: &NodeLink<T>, val: &T) -> NodeLink<T> {
+    bound_impl(root, |node_val| node_val >= val)
+}
+
+/// find the node with the smallest value which is greater than a given one
+pub fn upper_bound<T: Ord>(root: &NodeLink<T>, val: &T) -> NodeLink<T> {
+    bound_impl(root, |node_val| node_val > val)
+}
+
+/// find the leftmost node satisfying the predicate, which must be monotonic in the node values
+fn bound_impl<T>(root: &NodeLink<T>, fits: impl Fn(&T) -> bool) -> NodeLink<T> {
+    let mut result = None;
+    let mut current = root.clone();
+
+    while let Some(node) = current {
+        let node_ref = node.borrow();
+        if fits(&node_ref.val) {
+            current = node_ref.left.clone();
+            result = Some(node.clone());
+        } else {
+            current = node_ref.right.clone();
+        }
+    }
+
+    result
+}
+
+/// check if the tree is a valid binary search tree with distinct values
+pub fn is_valid_bst<T: Ord>(root: &NodeLink<T>) -> bool {
+    let mut previous: NodeLink<T> = None;
+
+    for node in inorder(root) {
+        if let Some(previous) = &previous {
+            if previous.borrow().val >= node.borrow().val {
+                return false;
+            }
+        }
+        previous = Some(node);
+    }
+
+    true
+}
+
+/// find the node with the k-th smallest value (counting from 1)
+pub fn kth_smallest<T>(root: &NodeLink<T>, k: usize) -> NodeLink<T> {
+    if k == 0 {
+        return None;
+    }
+    inorder(root).nth(k - 1)
+}
+
+/// build a height-balanced binary search tree from sorted values
+pub fn from_sorted<T: Clone>(values: &[T]) -> NodeLink<T> {
+    if values.is_empty() {
+        return None;
+    }
+
+    let mid = values.len() / 2;
+    TreeNode::new_link(
+        values[mid].clone(),
+        from_sorted(&values[..mid]),
+        from_sorted(&values[mid + 1..]),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::binary_tree::strategies::{arb_bst, arb_tree};
+    use crate::common::binary_tree::{format_tree, level_order, parse_tree};
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use rstest::rstest;
+    use std::collections::BTreeSet;
+
+    fn values(root: &NodeLink<i32>) -> Vec<i32> {
+        inorder(root).map(|node| node.borrow().val).collect()
+    }
+
+    fn height(root: &NodeLink<i32>) -> usize {
+        level_order(root)
+            .map(|(depth, _)| depth + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn val_of(link: NodeLink<i32>) -> Option<i32> {
+        link.map(|node| node.borrow().val)
+    }
+
+    #[derive(Debug, Clone)]
+    enum Operation {
+        Insert(i32),
+        Delete(i32),
+    }
+
+    fn operations() -> impl Strategy<Value = Vec<Operation>> {
+        vec(
+            prop_oneof![
+                (-50..50).prop_map(Operation::Insert),
+                (-50..50).prop_map(Operation::Delete),
+            ],
+            0..100,
+        )
+    }
+
+    #[rstest]
+    #[case("[5,3,6,2,4,null,7]", 3, "[5,4,6,2,null,null,7]")]
+    #[case("[5,3,6,2,4,null,7]", 5, "[6,3,7,2,4]")]
+    #[case("[5,3,6,2,4,null,7]", 0, "[5,3,6,2,4,null,7]")]
+    #[case("[5,3,8,2,4,6,9,null,null,null,null,null,7]", 5, "[6,3,8,2,4,7,9]")]
+    #[case("[1]", 1, "[]")]
+    fn delete_relinks_nodes(#[case] input: &str, #[case] val: i32, #[case] expected: &str) {
+        let mut tree = parse_tree::<i32>(input).unwrap();
+        delete(&mut tree, &val);
+        assert_eq!(format_tree(&tree), expected);
+    }
+
+    #[test]
+    fn bounds_on_empty_tree() {
+        assert_eq!(lower_bound::<i32>(&None, &1), None);
+        assert_eq!(upper_bound::<i32>(&None, &1), None);
+        assert_eq!(kth_smallest::<i32>(&None, 1), None);
+    }
+
+    #[rstest]
+    #[case("[2,1,3]", true)]
+    #[case("[5,1,4,null,null,3,6]", false)]
+    #[case("[2,2,2]", false)]
+    #[case("[5,4,6,null,null,3,7]", false)]
+    #[case("[]", true)]
+    fn validity(#[case] input: &str, #[case] expected: bool) {
+        let tree = parse_tree::<i32>(input).unwrap();
+        assert_eq!(is_valid_bst(&tree), expected);
+    }
+
+    proptest! {
+        #[test]
+        fn insert_delete_match_btree_set(ops in operations()) {
+            let mut tree = None;
+            let mut set = BTreeSet::new();
+
+            for op in ops {
+                match op {
+                    Operation::Insert(val) => prop_assert_eq!(insert(&mut tree, val), set.insert(val)),
+                    Operation::Delete(val) => prop_assert_eq!(delete(&mut tree, &val), set.remove(&val)),
+                }
+
+                prop_assert!(is_valid_bst(&tree));
+                prop_assert_eq!(values(&tree), set.iter().cloned().collect::<Vec<_>>());
+            }
+        }
+
+        #[test]
+        fn queries_match_btree_set(tree in arb_bst(-100..100, 50, 8), val in -110..110) {
+            let set: BTreeSet<i32> = values(&tree).into_iter().collect();
+
+            prop_assert_eq!(val_of(search(&tree, &val)), set.get(&val).cloned());
+            prop_assert_eq!(val_of(lower_bound(&tree, &val)), set.range(val..).next().cloned());
+            prop_assert_eq!(
+                val_of(upper_bound(&tree, &val)),
+                set.range(val + 1..).next().cloned()
+            );
+        }
+
+        #[test]
+        fn kth_smallest_matches_btree_set(tree in arb_bst(-100..100, 50, 8), k in 0usize..55) {
+            let set: BTreeSet<i32> = values(&tree).into_iter().collect();
+            let expected = if k == 0 { None } else { set.iter().nth(k - 1).cloned() };
+
+            prop_assert_eq!(val_of(kth_smallest(&tree, k)), expected);
+        }
+
+        #[test]
+        fn arbitrary_tree_is_valid_if_inorder_is_sorted(tree in arb_tree(0..20, 10, 4)) {
+            let vals = values(&tree);
+            let sorted = vals.windows(2).all(|pair| pair[0] < pair[1]);
+
+            prop_assert_eq!(is_valid_bst(&tree), sorted);
+        }
+
+        #[test]
+        fn from_sorted_is_balanced(set in proptest::collection::btree_set(-1000..1000, 0..200)) {
+            let sorted: Vec<i32> = set.into_iter().collect();
+            let tree = from_sorted(&sorted);
+
+            let min_height = (usize::BITS - sorted.len().leading_zeros()) as usize;
+
+            prop_assert!(is_valid_bst(&tree));
+            prop_assert_eq!(values(&tree), sorted);
+            prop_assert_eq!(height(&tree), min_height);
+        }
+    }
+}
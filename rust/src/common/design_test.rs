@@ -0,0 +1,270 @@
+//! Support code for `design_test!`, which replays examples of leetcode "design" problems.
+
+use crate::common::leetcode_format::{parse_leetcode, FromLeetcode, RawValue, ToLeetcode};
+
+/// one call of a design problem example
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DesignStep {
+    /// position of the call in the example (the constructor call has index 0)
+    pub index: usize,
+    /// name of the class or the method as leetcode writes it, e.g. `buildDict`
+    pub name: String,
+    pub args: Vec<RawValue>,
+    pub expected: RawValue,
+}
+
+/// parse leetcode arrays of call names, call arguments and expected outputs into steps,
+/// e.g. `["SnapshotArray","set","snap"]`, `[[3],[0,5],[]]` and `[null,null,0]`.
+/// Panics if they are malformed or have different lengths
+pub fn parse_steps(calls: &str, args: &str, expected: &str) -> Vec<DesignStep> {
+    let calls: Vec<String> =
+        parse_leetcode(calls).unwrap_or_else(|error| panic!("invalid calls: {}", error));
+    let args: Vec<Vec<RawValue>> =
+        parse_leetcode(args).unwrap_or_else(|error| panic!("invalid arguments: {}", error));
+    let expected: Vec<RawValue> =
+        parse_leetcode(expected).unwrap_or_else(|error| panic!("invalid outputs: {}", error));
+
+    assert!(
+        calls.len() == args.len() && calls.len() == expected.len(),
+        "example has {} calls, {} argument lists and {} outputs",
+        calls.len(),
+        args.len(),
+        expected.len()
+    );
+
+    calls
+        .into_iter()
+        .zip(args)
+        .zip(expected)
+        .enumerate()
+        .map(|(index, ((name, args), expected))| DesignStep {
+            index,
+            name,
+            args,
+            expected,
+        })
+        .collect()
+}
+
+/// leetcode name of a rust method, e.g. `buildDict` for `build_dict`
+pub fn camel_case(name: &str) -> String {
+    let mut words = name.split('_');
+    let mut result = words.next().unwrap_or_default().to_string();
+
+    for word in words {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            result.extend(first.to_uppercase());
+            result.push_str(chars.as_str());
+        }
+    }
+
+    result
+}
+
+impl DesignStep {
+    /// the call as written in the example, e.g. `step 2: get(0,5)`
+    fn describe(&self) -> String {
+        let args: Vec<&str> = self.args.iter().map(|arg| arg.0.as_str()).collect();
+        format!("step {}: {}({})", self.index, self.name, args.join(","))
+    }
+
+    /// arguments of the call, to be parsed one by one
+    pub fn arguments(&self) -> StepArgs<'_> {
+        StepArgs {
+            step: self,
+            next: 0,
+        }
+    }
+
+    /// panic unless the step calls the expected class or method
+    pub fn expect_name(&self, name: &str) {
+        assert!(
+            self.name == name,
+            "{}: expected a call of `{}`",
+            self.describe(),
+            name
+        );
+    }
+
+    /// panic for a call of a method which isn't listed
+    pub fn unknown_method(&self) -> ! {
+        panic!("{}: unknown method `{}`", self.describe(), self.name);
+    }
+
+    /// panic unless the call returned the expected output
+    pub fn check<R: FromLeetcode + ToLeetcode + PartialEq>(&self, actual: R) {
+        let expected: R = self.expected.parse().unwrap_or_else(|error| {
+            panic!(
+                "{}: invalid expected output `{}`: {}",
+                self.describe(),
+                self.expected.0,
+                error
+            )
+        });
+
+        assert!(
+            actual == expected,
+            "{}: returned {}, expected {}",
+            self.describe(),
+            actual.to_leetcode(),
+            expected.to_leetcode()
+        );
+    }
+}
+
+/// arguments of a step, parsed in order
+pub struct StepArgs<'a> {
+    step: &'a DesignStep,
+    next: usize,
+}
+
+impl StepArgs<'_> {
+    /// parse the next argument. Panics if there are no arguments left or it's invalid
+    pub fn next_value<T: FromLeetcode>(&mut self) -> T {
+        let index = self.next;
+        self.next += 1;
+
+        let raw =
+            self.step.args.get(index).unwrap_or_else(|| {
+                panic!("{}: argument {} is missing", self.step.describe(), index)
+            });
+        raw.parse().unwrap_or_else(|error| {
+            panic!(
+                "{}: invalid argument {}: {}",
+                self.step.describe(),
+                index,
+                error
+            )
+        })
+    }
+
+    /// panic if there are arguments left
+    pub fn finish(self) {
+        assert!(
+            self.next == self.step.args.len(),
+            "{}: expected {} arguments",
+            self.step.describe(),
+            self.next
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    struct Counter {
+        value: i64,
+    }
+
+    impl Counter {
+        fn new(start: i64) -> Self {
+            Counter { value: start }
+        }
+
+        fn add_all(&mut self, values: Vec<i64>) {
+            self.value += values.iter().sum::<i64>();
+        }
+
+        fn get(&self) -> i64 {
+            self.value
+        }
+
+        fn is_negative(&self) -> bool {
+            self.value < 0
+        }
+    }
+
+    #[rstest]
+    #[case("build_dict", "buildDict")]
+    #[case("to_string", "toString")]
+    #[case("f", "f")]
+    fn camel_case_names(#[case] name: &str, #[case] expected: &str) {
+        assert_eq!(camel_case(name), expected);
+    }
+
+    #[test]
+    fn steps_are_parsed() {
+        let steps = parse_steps(r#"["Counter", "get"]"#, "[[1], []]", "[null, 1]");
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[1].index, 1);
+        assert_eq!(steps[1].name, "get");
+        assert!(steps[1].args.is_empty());
+        assert_eq!(steps[1].expected, RawValue("1".to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "example has 2 calls, 1 argument lists and 2 outputs")]
+    fn lengths_are_checked() {
+        parse_steps(r#"["Counter","get"]"#, "[[1]]", "[null,1]");
+    }
+
+    #[test]
+    fn example_is_replayed() {
+        crate::design_test!(
+            Counter { new(start), add_all(values), get(), is_negative() },
+            r#"["Counter","get","addAll","isNegative","addAll","get"]"#,
+            "[[2],[],[[-1,-3]],[],[[]],[]]",
+            "[null,2,null,true,null,-2]",
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "step 3: get(): returned -2, expected 2")]
+    fn first_mismatch_is_reported() {
+        crate::design_test!(
+            Counter { new(start), add_all(values), get() },
+            r#"["Counter","get","addAll","get","get"]"#,
+            "[[2],[],[[-1,-3]],[],[]]",
+            "[null,2,null,2,3]",
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "step 1: reset(): unknown method `reset`")]
+    fn unknown_methods_are_reported() {
+        crate::design_test!(
+            Counter { new(start), get() },
+            r#"["Counter","reset"]"#,
+            "[[2],[]]",
+            "[null,null]",
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "step 0: Counter(2): expected a call of `Total`")]
+    fn class_name_is_checked() {
+        crate::design_test!(
+            Counter as "Total" { new(start), get() },
+            r#"["Counter"]"#,
+            "[[2]]",
+            "[null]",
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "step 1: get(1): expected 0 arguments")]
+    fn extra_arguments_are_reported() {
+        crate::design_test!(
+            Counter { new(start), get() },
+            r#"["Counter","get"]"#,
+            "[[2],[1]]",
+            "[null,2]",
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "step 1: addAll(\"a\"): invalid argument 0: expected `[` at position 0"
+    )]
+    fn invalid_arguments_are_reported() {
+        crate::design_test!(
+            Counter { new(start), add_all(values) },
+            r#"["Counter","addAll"]"#,
+            r#"[[2],["a"]]"#,
+            "[null,null]",
+        );
+    }
+}
@@ -0,0 +1,309 @@
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::rc::{Rc, Weak};
+use std::str::FromStr;
+
+use crate::common::linked_list::{strip_brackets, ParseListError};
+
+pub type DoublyLink<T> = Option<Rc<RefCell<DoublyListNode<T>>>>;
+
+/// Node of a doubly linked list which may also own a child list (multilevel list).
+///
+/// Links to previous nodes are weak, so that they don't make reference cycles:
+/// nodes are owned by the `next` and `child` links
+#[derive(Debug)]
+pub struct DoublyListNode<T> {
+    pub val: T,
+    pub prev: Option<Weak<RefCell<DoublyListNode<T>>>>,
+    pub next: DoublyLink<T>,
+    pub child: DoublyLink<T>,
+}
+
+impl<T> DoublyListNode<T> {
+    #[inline]
+    pub fn new(val: T) -> Self {
+        DoublyListNode {
+            val,
+            prev: None,
+            next: None,
+            child: None,
+        }
+    }
+}
+
+impl<T> Drop for DoublyListNode<T> {
+    fn drop(&mut self) {
+        let mut stack: Vec<_> = [self.next.take(), self.child.take()]
+            .into_iter()
+            .flatten()
+            .collect();
+
+        while let Some(node) = stack.pop() {
+            // nodes which are still referenced elsewhere keep their links
+            if let Ok(cell) = Rc::try_unwrap(node) {
+                let mut node = cell.into_inner();
+                stack.extend(node.next.take());
+                stack.extend(node.child.take());
+            }
+        }
+    }
+}
+
+/// link the values into a doubly linked list, returning all its nodes
+fn link_level<T>(values: Vec<T>) -> Vec<Rc<RefCell<DoublyListNode<T>>>> {
+    let nodes: Vec<_> = values
+        .into_iter()
+        .map(|val| Rc::new(RefCell::new(DoublyListNode::new(val))))
+        .collect();
+
+    for pair in nodes.windows(2) {
+        pair[0].borrow_mut().next = Some(pair[1].clone());
+        pair[1].borrow_mut().prev = Some(Rc::downgrade(&pair[0]));
+    }
+
+    nodes
+}
+
+/// nodes of the list along `next` links
+fn level_nodes<T>(head: &DoublyLink<T>) -> Vec<Rc<RefCell<DoublyListNode<T>>>> {
+    std::iter::successors(head.clone(), |node| node.borrow().next.clone()).collect()
+}
+
+/// parse a multilevel list from leetcode format, e.g. `[1,2,3,null,null,4,5]`.
+///
+/// Levels are separated by `null`, and every extra `null` before a level
+/// moves its parent one node further along the previous level
+pub fn parse_multilevel_list<T: FromStr>(input: &str) -> Result<DoublyLink<T>, ParseListError> {
+    let inner = strip_brackets(input)?;
+    if inner.is_empty() {
+        return Ok(None);
+    }
+    let tokens: Vec<&str> = inner.split(',').map(str::trim).collect();
+
+    let mut head = None;
+    let mut parents: Vec<Rc<RefCell<DoublyListNode<T>>>> = vec![];
+    let mut index = 0;
+    while index < tokens.len() {
+        // the first null ends the previous level, the rest are the parent offset
+        let mut offset = 0;
+        while index < tokens.len() && tokens[index] == "null" {
+            offset += 1;
+            index += 1;
+        }
+        if index == tokens.len() {
+            break;
+        }
+        let parent = match (head.is_some(), offset) {
+            (false, 0) => None,
+            (true, offset) if offset > 0 && offset <= parents.len() => Some(&parents[offset - 1]),
+            _ => {
+                return Err(ParseListError::DanglingValue {
+                    index,
+                    token: tokens[index].to_owned(),
+                })
+            }
+        };
+
+        let mut values = vec![];
+        while index < tokens.len() && tokens[index] != "null" {
+            let token = tokens[index];
+            let value = token
+                .parse::<T>()
+                .map_err(|_| ParseListError::InvalidValue {
+                    index,
+                    token: token.to_owned(),
+                })?;
+            values.push(value);
+            index += 1;
+        }
+
+        let level = link_level(values);
+        match parent {
+            Some(parent) => parent.borrow_mut().child = level.first().cloned(),
+            None => head = level.first().cloned(),
+        }
+        parents = level;
+    }
+
+    Ok(head)
+}
+
+/// format a multilevel list in leetcode format without spaces, e.g. `[1,2,3,null,null,4,5]`.
+/// Panics if a level has more than one child, since the format can't represent it
+pub fn format_multilevel_list<T: Display>(head: &DoublyLink<T>) -> String {
+    let mut tokens = vec![];
+
+    let mut level = head.clone();
+    let mut depth = 0;
+    while level.is_some() {
+        let nodes = level_nodes(&level);
+        tokens.extend(nodes.iter().map(|node| node.borrow().val.to_string()));
+
+        let mut children = nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.borrow().child.is_some());
+        level = match children.next() {
+            Some((offset, node)) => {
+                tokens.extend(std::iter::repeat_n("null".to_string(), offset + 1));
+                node.borrow().child.clone()
+            }
+            None => None,
+        };
+        assert!(
+            children.next().is_none(),
+            "level {} has more than one child",
+            depth
+        );
+        depth += 1;
+    }
+
+    format!("[{}]", tokens.join(","))
+}
+
+/// values along `next` links, ignoring children
+pub fn list_values<T: Clone>(head: &DoublyLink<T>) -> Vec<T> {
+    level_nodes(head)
+        .iter()
+        .map(|node| node.borrow().val.clone())
+        .collect()
+}
+
+/// values in the order of the flattened list: every child list goes right after its parent
+pub fn flattened_values<T: Clone>(head: &DoublyLink<T>) -> Vec<T> {
+    let mut values = vec![];
+
+    let mut stack: Vec<_> = head.iter().cloned().collect();
+    while let Some(node) = stack.pop() {
+        let node = node.borrow();
+        values.push(node.val.clone());
+        stack.extend(node.next.clone());
+        stack.extend(node.child.clone());
+    }
+
+    values
+}
+
+/// find the first node along `next` links whose `prev` link doesn't point to its predecessor
+pub fn find_broken_prev<T>(head: &DoublyLink<T>) -> Option<usize> {
+    let nodes = level_nodes(head);
+
+    nodes.iter().enumerate().position(|(index, node)| {
+        let prev = node.borrow().prev.as_ref().and_then(Weak::upgrade);
+        match (index.checked_sub(1), prev) {
+            (None, prev) => prev.is_some(),
+            (Some(_), None) => true,
+            (Some(prev_index), Some(prev)) => !Rc::ptr_eq(&nodes[prev_index], &prev),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(
+        "[1,2,3,4,5,6,null,null,null,7,8,9,10,null,null,11,12]",
+        vec![1, 2, 3, 7, 8, 11, 12, 9, 10, 4, 5, 6]
+    )]
+    #[case("[1,2,null,3]", vec![1, 3, 2])]
+    #[case("[1]", vec![1])]
+    #[case("[]", vec![])]
+    fn parse_format(#[case] input: &str, #[case] flattened: Vec<i32>) {
+        let list = parse_multilevel_list::<i32>(input).unwrap();
+        assert_eq!(format_multilevel_list(&list), input);
+        assert_eq!(flattened_values(&list), flattened);
+        assert_eq!(find_broken_prev(&list), None);
+    }
+
+    #[rstest]
+    #[case(
+        "[1,2,null,null,null,3]",
+        "value `3` at index 5 has no node to attach to"
+    )]
+    #[case("[null,1]", "value `1` at index 1 has no node to attach to")]
+    #[case("[1,x]", "invalid node value `x` at index 1")]
+    #[case("1,2", "list must be enclosed in square brackets")]
+    fn parse_errors(#[case] input: &str, #[case] expected: &str) {
+        let error = parse_multilevel_list::<i32>(input).unwrap_err();
+        assert_eq!(error.to_string(), expected);
+    }
+
+    #[test]
+    fn trailing_nulls_are_ignored() {
+        let list = parse_multilevel_list::<i32>("[1,2,null,null]").unwrap();
+        assert_eq!(format_multilevel_list(&list), "[1,2]");
+    }
+
+    #[test]
+    fn broken_prev_is_found() {
+        let list = parse_multilevel_list::<i32>("[1,2,3,4]").unwrap();
+        let nodes = level_nodes(&list);
+        nodes[2].borrow_mut().prev = Some(Rc::downgrade(&nodes[0]));
+        assert_eq!(find_broken_prev(&list), Some(2));
+
+        nodes[2].borrow_mut().prev = None;
+        assert_eq!(find_broken_prev(&list), Some(2));
+
+        nodes[2].borrow_mut().prev = Some(Rc::downgrade(&nodes[1]));
+        nodes[0].borrow_mut().prev = Some(Rc::downgrade(&nodes[3]));
+        assert_eq!(find_broken_prev(&list), Some(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "level 0 has more than one child")]
+    fn several_children_are_not_formatted() {
+        let list = parse_multilevel_list::<i32>("[1,2,null,3]").unwrap();
+        let nodes = level_nodes(&list);
+        nodes[1].borrow_mut().child = Some(Rc::new(RefCell::new(DoublyListNode::new(4))));
+        format_multilevel_list(&list);
+    }
+
+    #[test]
+    fn long_list_is_dropped() {
+        let len = 1_000_000;
+        let head = link_level((0..len).collect()).first().cloned();
+        let mut child = link_level((0..len).collect()).first().cloned();
+        for node in level_nodes(&head).iter().rev() {
+            node.borrow_mut().child = child.take();
+            child = Some(Rc::new(RefCell::new(DoublyListNode::new(0))));
+        }
+
+        assert_eq!(list_values(&head).len(), len as usize);
+    }
+
+    proptest! {
+        #[test]
+        fn format_parse(
+            levels in vec((vec(-100..100, 1..10), any::<prop::sample::Index>()), 0..6)
+        ) {
+            let mut tokens = vec![];
+            let mut expected_levels = vec![];
+            let mut parent_len = 0;
+            for (level, offset) in levels {
+                if parent_len > 0 {
+                    let offset = offset.index(parent_len);
+                    tokens.extend(std::iter::repeat_n("null".to_string(), offset + 1));
+                }
+                parent_len = level.len();
+                tokens.extend(level.iter().map(i32::to_string));
+                expected_levels.push(level);
+            }
+            let input = format!("[{}]", tokens.join(","));
+
+            let list = parse_multilevel_list::<i32>(&input).unwrap();
+            prop_assert_eq!(format_multilevel_list(&list), input);
+            prop_assert_eq!(find_broken_prev(&list), None);
+
+            let mut flattened = flattened_values(&list);
+            let mut expected: Vec<i32> = expected_levels.into_iter().flatten().collect();
+            flattened.sort();
+            expected.sort();
+            prop_assert_eq!(flattened, expected);
+        }
+    }
+}
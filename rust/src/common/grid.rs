@@ -0,0 +1,369 @@
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+/// offsets of the cells sharing a side with a cell
+const SIDE_OFFSETS: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// offsets of the cells sharing a side or a corner with a cell
+const ALL_OFFSETS: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Rectangular grid of cells stored row by row in a single vector.
+///
+/// Cells are addressed by `(row, col)` coordinates or by their flat index `row * cols + col`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    rows: usize,
+    cols: usize,
+    cells: Vec<T>,
+}
+
+/// reason why rows can't make a grid
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum GridError {
+    /// row at a given index has a different length than the first one
+    RaggedRow {
+        row: usize,
+        len: usize,
+        expected: usize,
+    },
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::RaggedRow { row, len, expected } => {
+                write!(f, "row {} has {} cells instead of {}", row, len, expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+impl<T: Clone> Grid<T> {
+    /// grid with every cell set to the value
+    pub fn new(rows: usize, cols: usize, value: T) -> Self {
+        Grid {
+            rows,
+            cols,
+            cells: vec![value; rows * cols],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    #[inline]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    #[inline]
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    #[inline]
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        row < self.rows && col < self.cols
+    }
+
+    /// the cell, or `None` if the coordinates are out of the grid
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        self.contains(row, col)
+            .then(|| &self.cells[self.index_of(row, col)])
+    }
+
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        if self.contains(row, col) {
+            let index = self.index_of(row, col);
+            Some(&mut self.cells[index])
+        } else {
+            None
+        }
+    }
+
+    /// flat index of the cell
+    #[inline]
+    pub fn index_of(&self, row: usize, col: usize) -> usize {
+        debug_assert!(self.contains(row, col));
+        row * self.cols + col
+    }
+
+    /// coordinates of the cell with the flat index
+    #[inline]
+    pub fn coords_of(&self, index: usize) -> (usize, usize) {
+        debug_assert!(index < self.cells.len());
+        (index / self.cols, index % self.cols)
+    }
+
+    /// cells of the row as a slice. Panics if the row is out of the grid
+    pub fn row(&self, row: usize) -> &[T] {
+        assert!(row < self.rows, "row {} is out of {} rows", row, self.rows);
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        assert!(row < self.rows, "row {} is out of {} rows", row, self.rows);
+        &mut self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    /// cells of the column from top to bottom. Panics if the column is out of the grid
+    pub fn column(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(
+            col < self.cols,
+            "column {} is out of {} columns",
+            col,
+            self.cols
+        );
+        self.cells.iter().skip(col).step_by(self.cols)
+    }
+
+    /// coordinates of all cells row by row
+    pub fn coords(&self) -> impl Iterator<Item = (usize, usize)> {
+        let (rows, cols) = (self.rows, self.cols);
+        (0..rows).flat_map(move |row| (0..cols).map(move |col| (row, col)))
+    }
+
+    /// cells row by row
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    /// coordinates of the cells in the grid which share a side with the cell
+    pub fn neighbors4(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbors(row, col, &SIDE_OFFSETS)
+    }
+
+    /// coordinates of the cells in the grid which share a side or a corner with the cell
+    pub fn neighbors8(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbors(row, col, &ALL_OFFSETS)
+    }
+
+    fn neighbors(
+        &self,
+        row: usize,
+        col: usize,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let (rows, cols) = (self.rows, self.cols);
+        offsets.iter().filter_map(move |&(row_offset, col_offset)| {
+            let row = row.checked_add_signed(row_offset)?;
+            let col = col.checked_add_signed(col_offset)?;
+            (row < rows && col < cols).then_some((row, col))
+        })
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        self.get(row, col).unwrap_or_else(|| {
+            panic!(
+                "cell ({}, {}) is out of the {}x{} grid",
+                row, col, self.rows, self.cols
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        let (rows, cols) = (self.rows, self.cols);
+        self.get_mut(row, col).unwrap_or_else(|| {
+            panic!(
+                "cell ({}, {}) is out of the {}x{} grid",
+                row, col, rows, cols
+            )
+        })
+    }
+}
+
+impl<T> TryFrom<Vec<Vec<T>>> for Grid<T> {
+    type Error = GridError;
+
+    fn try_from(values: Vec<Vec<T>>) -> Result<Self, GridError> {
+        let rows = values.len();
+        let cols = values.first().map_or(0, Vec::len);
+
+        let mut cells = Vec::with_capacity(rows * cols);
+        for (row, values) in values.into_iter().enumerate() {
+            if values.len() != cols {
+                return Err(GridError::RaggedRow {
+                    row,
+                    len: values.len(),
+                    expected: cols,
+                });
+            }
+            cells.extend(values);
+        }
+
+        Ok(Grid { rows, cols, cells })
+    }
+}
+
+impl<T> From<Grid<T>> for Vec<Vec<T>> {
+    fn from(grid: Grid<T>) -> Self {
+        let mut cells = grid.cells.into_iter();
+        (0..grid.rows)
+            .map(|_| cells.by_ref().take(grid.cols).collect())
+            .collect()
+    }
+}
+
+/// parse a board of chars with one row per line, e.g. `"AB\nCD"`.
+/// Lines are trimmed and blank lines are skipped, so that boards can be indented
+impl FromStr for Grid<char> {
+    type Err = GridError;
+
+    fn from_str(input: &str) -> Result<Self, GridError> {
+        let rows: Vec<Vec<char>> = input
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| line.chars().collect())
+            .collect();
+
+        Grid::try_from(rows)
+    }
+}
+
+/// format a board of chars with one row per line
+impl Display for Grid<char> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines: Vec<String> = (0..self.rows)
+            .map(|row| self.row(row).iter().collect())
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{grid, vec2d};
+    use rstest::rstest;
+
+    #[test]
+    fn cells_are_addressed() {
+        let mut grid = grid![[1, 2, 3], [4, 5, 6]];
+        assert_eq!((grid.rows(), grid.cols()), (2, 3));
+        assert_eq!(grid[(1, 0)], 4);
+        assert_eq!(grid.get(1, 2), Some(&6));
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(grid.get(0, 3), None);
+
+        grid[(0, 1)] = 7;
+        *grid.get_mut(1, 1).unwrap() += 1;
+        assert_eq!(Vec::from(grid), vec2d![[1, 7, 3], [4, 6, 6]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "cell (0, 3) is out of the 2x3 grid")]
+    fn index_is_checked() {
+        let grid = Grid::new(2, 3, 0);
+        let _ = grid[(0, 3)];
+    }
+
+    #[test]
+    fn flat_indices_are_converted() {
+        let grid = Grid::new(3, 4, ());
+        for (index, (row, col)) in grid.coords().enumerate() {
+            assert_eq!(grid.index_of(row, col), index);
+            assert_eq!(grid.coords_of(index), (row, col));
+        }
+        assert_eq!(grid.coords().count(), 12);
+    }
+
+    #[test]
+    fn rows_and_columns_are_viewed() {
+        let mut grid = grid![[1, 2, 3], [4, 5, 6]];
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![3, 6]);
+        assert_eq!(
+            grid.column(0).rev().copied().collect::<Vec<_>>(),
+            vec![4, 1]
+        );
+
+        grid.row_mut(0).reverse();
+        assert_eq!(
+            grid.iter().copied().collect::<Vec<_>>(),
+            vec![3, 2, 1, 4, 5, 6]
+        );
+    }
+
+    #[rstest]
+    #[case((0, 0), vec![(0, 1), (1, 0)], vec![(0, 1), (1, 0), (1, 1)])]
+    #[case((1, 1), vec![(0, 1), (1, 0), (1, 2), (2, 1)], vec![(0, 0), (0, 1), (0, 2), (1, 0), (1, 2), (2, 0), (2, 1), (2, 2)])]
+    #[case((2, 1), vec![(1, 1), (2, 0), (2, 2)], vec![(1, 0), (1, 1), (1, 2), (2, 0), (2, 2)])]
+    fn neighbors_are_in_grid(
+        #[case] cell: (usize, usize),
+        #[case] expected4: Vec<(usize, usize)>,
+        #[case] expected8: Vec<(usize, usize)>,
+    ) {
+        let (row, col) = cell;
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(grid.neighbors4(row, col).collect::<Vec<_>>(), expected4);
+        assert_eq!(grid.neighbors8(row, col).collect::<Vec<_>>(), expected8);
+    }
+
+    #[test]
+    fn single_cell_has_no_neighbors() {
+        let grid = Grid::new(1, 1, 0);
+        assert_eq!(grid.neighbors8(0, 0).count(), 0);
+    }
+
+    #[test]
+    fn rows_are_converted() {
+        let grid = Grid::try_from(vec2d![[1], [2], [3]]).unwrap();
+        assert_eq!((grid.rows(), grid.cols()), (3, 1));
+        assert_eq!(Vec::from(grid), vec2d![[1], [2], [3]]);
+
+        let grid: Grid<i32> = grid![[], []];
+        assert_eq!((grid.rows(), grid.cols()), (2, 0));
+        assert_eq!(Vec::from(grid), vec2d![[], []] as Vec<Vec<i32>>);
+
+        let grid: Grid<i32> = grid![];
+        assert_eq!((grid.rows(), grid.cols()), (0, 0));
+    }
+
+    #[test]
+    fn ragged_rows_are_rejected() {
+        let error = Grid::try_from(vec2d![[1, 2], [3, 4], [5]]).unwrap_err();
+        assert_eq!(error.to_string(), "row 2 has 1 cells instead of 2");
+    }
+
+    #[test]
+    fn boards_are_parsed_and_formatted() {
+        let board: Grid<char> = "
+            ABCE
+            SFCS
+            ADEE
+        "
+        .parse()
+        .unwrap();
+        assert_eq!((board.rows(), board.cols()), (3, 4));
+        assert_eq!(board[(1, 3)], 'S');
+        assert_eq!(board.to_string(), "ABCE\nSFCS\nADEE");
+
+        let error = "AB\nC".parse::<Grid<char>>().unwrap_err();
+        assert_eq!(
+            error,
+            GridError::RaggedRow {
+                row: 1,
+                len: 1,
+                expected: 2
+            }
+        );
+    }
+}
@@ -0,0 +1,675 @@
+//! Values in the format used by leetcode problem statements, e.g. `[[1,-2],[3]]`, `["ab","c"]` or `[1,null,2]`.
+//!
+//! [`FromLeetcode`] parses inputs, and [`ToLeetcode`] prints outputs the way the leetcode judge does.
+
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::rc::Rc;
+
+use crate::common::binary_tree::{format_tree_with, TreeNode};
+use crate::common::linked_list::{ListExt, ListNode};
+
+/// error of parsing a value, with byte positions in the input
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParseValueError {
+    /// input ended while something was expected
+    UnexpectedEnd { expected: &'static str },
+    /// unexpected character at a given position
+    UnexpectedChar {
+        position: usize,
+        found: char,
+        expected: &'static str,
+    },
+    /// token starting at a given position isn't a valid value
+    InvalidToken {
+        position: usize,
+        token: String,
+        expected: &'static str,
+    },
+    /// input continues after the value
+    TrailingInput { position: usize },
+}
+
+impl Display for ParseValueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseValueError::UnexpectedEnd { expected } => {
+                write!(f, "expected {} at the end of input", expected)
+            }
+            ParseValueError::UnexpectedChar {
+                position,
+                found,
+                expected,
+            } => write!(
+                f,
+                "expected {} at position {}, found `{}`",
+                expected, position, found
+            ),
+            ParseValueError::InvalidToken {
+                position,
+                token,
+                expected,
+            } => write!(
+                f,
+                "invalid {} `{}` at position {}",
+                expected, token, position
+            ),
+            ParseValueError::TrailingInput { position } => {
+                write!(
+                    f,
+                    "unexpected input after the value at position {}",
+                    position
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseValueError {}
+
+/// cursor over the input, shared by nested values
+pub struct LeetcodeParser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> LeetcodeParser<'a> {
+    pub fn new(input: &'a str) -> Self {
+        LeetcodeParser { input, position: 0 }
+    }
+
+    /// byte position of the next character
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.position..]
+    }
+
+    /// skip whitespace and get the next character without consuming it
+    pub fn peek(&mut self) -> Option<char> {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+        self.rest().chars().next()
+    }
+
+    /// consume the next character if it's the expected one
+    pub fn eat(&mut self, expected: char) -> bool {
+        let found = self.peek() == Some(expected);
+        if found {
+            self.position += expected.len_utf8();
+        }
+        found
+    }
+
+    /// error for the next character, which isn't what was expected
+    pub fn unexpected(&mut self, expected: &'static str) -> ParseValueError {
+        match self.peek() {
+            Some(found) => ParseValueError::UnexpectedChar {
+                position: self.position,
+                found,
+                expected,
+            },
+            None => ParseValueError::UnexpectedEnd { expected },
+        }
+    }
+
+    /// consume the expected character or fail
+    pub fn expect(
+        &mut self,
+        expected: char,
+        description: &'static str,
+    ) -> Result<(), ParseValueError> {
+        if self.eat(expected) {
+            Ok(())
+        } else {
+            Err(self.unexpected(description))
+        }
+    }
+
+    /// consume an unquoted token up to the next `,`, `]` or whitespace.
+    /// Returns the token and its position
+    pub fn token(&mut self, expected: &'static str) -> Result<(usize, &'a str), ParseValueError> {
+        self.peek();
+        let start = self.position;
+        let rest = self.rest();
+        let len = rest
+            .find(|ch: char| ch == ',' || ch == ']' || ch.is_whitespace())
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return Err(self.unexpected(expected));
+        }
+
+        self.position += len;
+        Ok((start, &rest[..len]))
+    }
+
+    /// consume a double quoted string with json escapes. Returns the string and its position
+    pub fn quoted(&mut self) -> Result<(usize, String), ParseValueError> {
+        self.expect('"', "`\"`")?;
+        let start = self.position - 1;
+
+        let mut value = String::new();
+        let mut chars = self.rest().char_indices();
+        loop {
+            let (offset, ch) = chars.next().ok_or(ParseValueError::UnexpectedEnd {
+                expected: "closing `\"`",
+            })?;
+            match ch {
+                '"' => {
+                    self.position += offset + 1;
+                    return Ok((start, value));
+                }
+                '\\' => {
+                    let escaped = chars.next().map(|(_, escaped)| escaped);
+                    let unescaped = match escaped {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('n') => '\n',
+                        Some('t') => '\t',
+                        Some('r') => '\r',
+                        _ => {
+                            return Err(ParseValueError::InvalidToken {
+                                position: self.position + offset,
+                                token: escaped.map_or("\\".to_string(), |ch| format!("\\{}", ch)),
+                                expected: "escape sequence",
+                            })
+                        }
+                    };
+                    value.push(unescaped);
+                }
+                ch => value.push(ch),
+            }
+        }
+    }
+
+    /// consume a value of any type without converting it. Returns the value's source text
+    pub fn raw_value(&mut self) -> Result<&'a str, ParseValueError> {
+        let start = match self.peek() {
+            Some(_) => self.position,
+            None => return Err(self.unexpected("value")),
+        };
+
+        match self.peek() {
+            Some('[') => {
+                Vec::<RawValue>::parse_value(self)?;
+            }
+            Some('"') => {
+                self.quoted()?;
+            }
+            _ => {
+                self.token("value")?;
+            }
+        }
+
+        Ok(&self.input[start..self.position])
+    }
+
+    /// check that only whitespace is left
+    pub fn finish(mut self) -> Result<(), ParseValueError> {
+        match self.peek() {
+            Some(_) => Err(ParseValueError::TrailingInput {
+                position: self.position,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// values which can be parsed from leetcode format
+pub trait FromLeetcode: Sized {
+    /// parse the value at the parser position, leaving the parser right after it
+    fn parse_value(parser: &mut LeetcodeParser) -> Result<Self, ParseValueError>;
+
+    /// parse the whole input as a single value
+    fn from_leetcode(input: &str) -> Result<Self, ParseValueError> {
+        let mut parser = LeetcodeParser::new(input);
+        let value = Self::parse_value(&mut parser)?;
+        parser.finish()?;
+        Ok(value)
+    }
+}
+
+/// parse the whole input as a single value, e.g. `parse_leetcode::<Vec<Vec<i32>>>("[[1,2],[3]]")`
+pub fn parse_leetcode<T: FromLeetcode>(input: &str) -> Result<T, ParseValueError> {
+    T::from_leetcode(input)
+}
+
+/// source text of a value of any type, to be parsed later
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawValue(pub String);
+
+impl RawValue {
+    /// parse the value as a given type
+    pub fn parse<T: FromLeetcode>(&self) -> Result<T, ParseValueError> {
+        T::from_leetcode(&self.0)
+    }
+}
+
+impl FromLeetcode for RawValue {
+    fn parse_value(parser: &mut LeetcodeParser) -> Result<Self, ParseValueError> {
+        parser.raw_value().map(|raw| RawValue(raw.to_owned()))
+    }
+}
+
+/// `null`, which leetcode prints for methods returning nothing
+impl FromLeetcode for () {
+    fn parse_value(parser: &mut LeetcodeParser) -> Result<Self, ParseValueError> {
+        match parser.token("null")? {
+            (_, "null") => Ok(()),
+            (position, token) => Err(ParseValueError::InvalidToken {
+                position,
+                token: token.to_owned(),
+                expected: "null",
+            }),
+        }
+    }
+}
+
+macro_rules! impl_from_leetcode_for_numbers {
+    ($($number:ty),*) => {
+        $(
+            impl FromLeetcode for $number {
+                fn parse_value(parser: &mut LeetcodeParser) -> Result<Self, ParseValueError> {
+                    let (position, token) = parser.token(stringify!($number))?;
+                    token.parse().map_err(|_| ParseValueError::InvalidToken {
+                        position,
+                        token: token.to_owned(),
+                        expected: stringify!($number),
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_from_leetcode_for_numbers!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64
+);
+
+impl FromLeetcode for bool {
+    fn parse_value(parser: &mut LeetcodeParser) -> Result<Self, ParseValueError> {
+        match parser.token("bool")? {
+            (_, "true") => Ok(true),
+            (_, "false") => Ok(false),
+            (position, token) => Err(ParseValueError::InvalidToken {
+                position,
+                token: token.to_owned(),
+                expected: "bool",
+            }),
+        }
+    }
+}
+
+impl FromLeetcode for String {
+    fn parse_value(parser: &mut LeetcodeParser) -> Result<Self, ParseValueError> {
+        parser.quoted().map(|(_, value)| value)
+    }
+}
+
+impl FromLeetcode for char {
+    fn parse_value(parser: &mut LeetcodeParser) -> Result<Self, ParseValueError> {
+        let (position, value) = parser.quoted()?;
+
+        let mut chars = value.chars();
+        match (chars.next(), chars.next()) {
+            (Some(ch), None) => Ok(ch),
+            _ => Err(ParseValueError::InvalidToken {
+                position,
+                token: format!("\"{}\"", value),
+                expected: "char",
+            }),
+        }
+    }
+}
+
+/// `null` or a value
+impl<T: FromLeetcode> FromLeetcode for Option<T> {
+    fn parse_value(parser: &mut LeetcodeParser) -> Result<Self, ParseValueError> {
+        if parser.rest().trim_start().starts_with("null") {
+            let (position, token) = parser.token("null")?;
+            if token != "null" {
+                return Err(ParseValueError::InvalidToken {
+                    position,
+                    token: token.to_owned(),
+                    expected: "null",
+                });
+            }
+            return Ok(None);
+        }
+
+        T::parse_value(parser).map(Some)
+    }
+}
+
+impl<T: FromLeetcode> FromLeetcode for Vec<T> {
+    fn parse_value(parser: &mut LeetcodeParser) -> Result<Self, ParseValueError> {
+        parser.expect('[', "`[`")?;
+
+        let mut values = vec![];
+        if parser.eat(']') {
+            return Ok(values);
+        }
+        loop {
+            values.push(T::parse_value(parser)?);
+            if !parser.eat(',') {
+                parser.expect(']', "`,` or `]`")?;
+                return Ok(values);
+            }
+        }
+    }
+}
+
+/// values which can be printed the way the leetcode judge prints them
+pub trait ToLeetcode {
+    /// append the value in leetcode format
+    fn write_leetcode(&self, out: &mut String);
+
+    /// the value in leetcode format, e.g. `[1,null,2]` or `["ab","c"]`
+    fn to_leetcode(&self) -> String {
+        let mut out = String::new();
+        self.write_leetcode(&mut out);
+        out
+    }
+}
+
+macro_rules! impl_to_leetcode_for_integers {
+    ($($integer:ty),*) => {
+        $(
+            impl ToLeetcode for $integer {
+                fn write_leetcode(&self, out: &mut String) {
+                    out.push_str(&self.to_string());
+                }
+            }
+        )*
+    };
+}
+
+impl_to_leetcode_for_integers!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, bool
+);
+
+/// floats are printed with 5 decimal places, e.g. `2.50000`
+macro_rules! impl_to_leetcode_for_floats {
+    ($($float:ty),*) => {
+        $(
+            impl ToLeetcode for $float {
+                fn write_leetcode(&self, out: &mut String) {
+                    out.push_str(&format!("{:.5}", self));
+                }
+            }
+        )*
+    };
+}
+
+impl_to_leetcode_for_floats!(f32, f64);
+
+impl ToLeetcode for () {
+    fn write_leetcode(&self, out: &mut String) {
+        out.push_str("null");
+    }
+}
+
+impl ToLeetcode for str {
+    fn write_leetcode(&self, out: &mut String) {
+        out.push('"');
+        for ch in self.chars() {
+            match ch {
+                '"' => out.push_str("\\\""),
+                '\\' => out.push_str("\\\\"),
+                '\n' => out.push_str("\\n"),
+                '\t' => out.push_str("\\t"),
+                '\r' => out.push_str("\\r"),
+                ch => out.push(ch),
+            }
+        }
+        out.push('"');
+    }
+}
+
+impl ToLeetcode for String {
+    fn write_leetcode(&self, out: &mut String) {
+        self.as_str().write_leetcode(out);
+    }
+}
+
+impl ToLeetcode for char {
+    fn write_leetcode(&self, out: &mut String) {
+        self.encode_utf8(&mut [0; 4]).write_leetcode(out);
+    }
+}
+
+impl<T: ToLeetcode + ?Sized> ToLeetcode for &T {
+    fn write_leetcode(&self, out: &mut String) {
+        (**self).write_leetcode(out);
+    }
+}
+
+impl<T: ToLeetcode + ?Sized> ToLeetcode for &mut T {
+    fn write_leetcode(&self, out: &mut String) {
+        (**self).write_leetcode(out);
+    }
+}
+
+/// `null` or a value
+impl<T: ToLeetcode> ToLeetcode for Option<T> {
+    fn write_leetcode(&self, out: &mut String) {
+        match self {
+            Some(value) => value.write_leetcode(out),
+            None => out.push_str("null"),
+        }
+    }
+}
+
+impl<T: ToLeetcode> ToLeetcode for [T] {
+    fn write_leetcode(&self, out: &mut String) {
+        out.push('[');
+        for (index, value) in self.iter().enumerate() {
+            if index > 0 {
+                out.push(',');
+            }
+            value.write_leetcode(out);
+        }
+        out.push(']');
+    }
+}
+
+impl<T: ToLeetcode, const N: usize> ToLeetcode for [T; N] {
+    fn write_leetcode(&self, out: &mut String) {
+        self.as_slice().write_leetcode(out);
+    }
+}
+
+impl<T: ToLeetcode> ToLeetcode for Vec<T> {
+    fn write_leetcode(&self, out: &mut String) {
+        self.as_slice().write_leetcode(out);
+    }
+}
+
+/// tree in level order with trailing nulls trimmed, `[]` for an empty tree
+impl<T: ToLeetcode> ToLeetcode for Option<Rc<RefCell<TreeNode<T>>>> {
+    fn write_leetcode(&self, out: &mut String) {
+        out.push_str(&format_tree_with(self, T::to_leetcode));
+    }
+}
+
+/// list values, `[]` for an empty list
+impl<T: ToLeetcode> ToLeetcode for Option<Box<ListNode<T>>> {
+    fn write_leetcode(&self, out: &mut String) {
+        self.values().collect::<Vec<_>>().write_leetcode(out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::binary_tree::{parse_tree, NodeLink};
+    use crate::common::linked_list::vec_to_list;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    #[test]
+    fn numbers() {
+        assert_eq!(parse_leetcode::<i32>("-42"), Ok(-42));
+        assert_eq!(
+            parse_leetcode::<i64>(" 9007199254740993 "),
+            Ok(9007199254740993)
+        );
+        assert_eq!(parse_leetcode::<u8>("255"), Ok(255));
+        assert_eq!(parse_leetcode::<f64>("-2.50000"), Ok(-2.5));
+        assert_eq!(parse_leetcode::<f64>("1e-5"), Ok(0.00001));
+        assert_eq!(
+            parse_leetcode::<Vec<Vec<i32>>>("[[1,-2,3],[],[ -4 , 5 ]]"),
+            Ok(vec![vec![1, -2, 3], vec![], vec![-4, 5]])
+        );
+    }
+
+    #[test]
+    fn booleans_strings_and_chars() {
+        assert_eq!(
+            parse_leetcode::<Vec<bool>>("[true,false]"),
+            Ok(vec![true, false])
+        );
+        assert_eq!(
+            parse_leetcode::<Vec<String>>(r#"["ab", "", "a \"b\"\\n"]"#),
+            Ok(vec![
+                "ab".to_string(),
+                "".to_string(),
+                "a \"b\"\\n".to_string()
+            ])
+        );
+        assert_eq!(
+            parse_leetcode::<Vec<Vec<char>>>(r#"[["A","B"],["é","\n"]]"#),
+            Ok(vec![vec!['A', 'B'], vec!['é', '\n']])
+        );
+    }
+
+    #[test]
+    fn nulls() {
+        assert_eq!(
+            parse_leetcode::<Vec<Option<i32>>>("[1,null,-2]"),
+            Ok(vec![Some(1), None, Some(-2)])
+        );
+        assert_eq!(
+            parse_leetcode::<Vec<Option<Vec<i32>>>>("[null,[1]]"),
+            Ok(vec![None, Some(vec![1])])
+        );
+        assert_eq!(
+            parse_leetcode::<Vec<Option<String>>>(r#"["null",null]"#),
+            Ok(vec![Some("null".to_string()), None])
+        );
+    }
+
+    #[test]
+    fn deep_nesting() {
+        assert_eq!(
+            parse_leetcode::<Vec<Vec<Vec<Vec<u8>>>>>("[[[[1]],[]],[[[2,3]]]]"),
+            Ok(vec![vec![vec![vec![1]], vec![]], vec![vec![vec![2, 3]]]])
+        );
+    }
+
+    #[rstest]
+    #[case("[1,2", "expected `,` or `]` at the end of input")]
+    #[case("[1;2]", "invalid i32 `1;2` at position 1")]
+    #[case("[1,,2]", "expected i32 at position 3, found `,`")]
+    #[case("[1,2]]", "unexpected input after the value at position 5")]
+    #[case("[1,2.5]", "invalid i32 `2.5` at position 3")]
+    #[case("1,2]", "expected `[` at position 0, found `1`")]
+    #[case("[nullx]", "invalid null `nullx` at position 1")]
+    #[case("", "expected `[` at the end of input")]
+    fn errors(#[case] input: &str, #[case] expected: &str) {
+        let error = parse_leetcode::<Vec<Option<i32>>>(input).unwrap_err();
+        assert_eq!(error.to_string(), expected);
+    }
+
+    #[rstest]
+    #[case(r#"["ab"#, "expected closing `\"` at the end of input")]
+    #[case(r#"["a\qb"]"#, "invalid escape sequence `\\q` at position 3")]
+    #[case(r#"[ab]"#, "expected `\"` at position 1, found `a`")]
+    fn string_errors(#[case] input: &str, #[case] expected: &str) {
+        let error = parse_leetcode::<Vec<String>>(input).unwrap_err();
+        assert_eq!(error.to_string(), expected);
+    }
+
+    #[test]
+    fn other_errors() {
+        let error = parse_leetcode::<Vec<char>>(r#"["a", "bc"]"#).unwrap_err();
+        assert_eq!(error.to_string(), "invalid char `\"bc\"` at position 6");
+
+        let error = parse_leetcode::<bool>("yes").unwrap_err();
+        assert_eq!(error.to_string(), "invalid bool `yes` at position 0");
+
+        let error = parse_leetcode::<u32>("-1").unwrap_err();
+        assert_eq!(error.to_string(), "invalid u32 `-1` at position 0");
+    }
+
+    #[test]
+    fn leetcode_output() {
+        assert_eq!((-42).to_leetcode(), "-42");
+        assert_eq!(2.5f64.to_leetcode(), "2.50000");
+        assert_eq!((-1.0f64 / 3.0).to_leetcode(), "-0.33333");
+        assert_eq!(vec![true, false].to_leetcode(), "[true,false]");
+        assert_eq!(
+            vec!["a \"b\"\\".to_string(), "".to_string()].to_leetcode(),
+            r#"["a \"b\"\\",""]"#
+        );
+        assert_eq!(vec![vec!['A'], vec![]].to_leetcode(), r#"[["A"],[]]"#);
+        assert_eq!(vec![Some(1), None].to_leetcode(), "[1,null]");
+    }
+
+    #[test]
+    fn trees_and_lists() {
+        let tree = parse_tree::<i32>("[1,null,2,3]").unwrap();
+        assert_eq!(tree.to_leetcode(), "[1,null,2,3]");
+        assert_eq!(NodeLink::<i32>::None.to_leetcode(), "[]");
+        assert_eq!(vec![tree, None].to_leetcode(), "[[1,null,2,3],[]]");
+
+        assert_eq!(vec_to_list(vec![1, 4, 5]).to_leetcode(), "[1,4,5]");
+        assert_eq!(vec_to_list::<i32>(vec![]).to_leetcode(), "[]");
+    }
+
+    #[test]
+    fn raw_values() {
+        let values = parse_leetcode::<Vec<RawValue>>(r#"[ [1, [2]] ,"a,]",null, -3 ]"#).unwrap();
+        let raw: Vec<&str> = values.iter().map(|value| value.0.as_str()).collect();
+        assert_eq!(raw, vec!["[1, [2]]", r#""a,]""#, "null", "-3"]);
+
+        assert!(values[0].parse::<Vec<i32>>().is_err());
+        assert_eq!(values[1].parse::<String>(), Ok("a,]".to_string()));
+        assert_eq!(values[3].parse::<i64>(), Ok(-3));
+        assert_eq!(values[2].parse::<()>(), Ok(()));
+        assert_eq!(().to_leetcode(), "null");
+
+        let error = parse_leetcode::<Vec<RawValue>>("[1,").unwrap_err();
+        assert_eq!(error.to_string(), "expected value at the end of input");
+    }
+
+    proptest! {
+        #[test]
+        fn format_parse(values in vec(vec(any::<Option<i64>>(), 0..5), 0..5)) {
+            let formatted = values.to_leetcode();
+            prop_assert_eq!(parse_leetcode::<Vec<Vec<Option<i64>>>>(&formatted), Ok(values));
+        }
+
+        #[test]
+        fn format_parse_strings(values in vec(any::<String>(), 0..5)) {
+            let formatted = values.to_leetcode();
+            prop_assert_eq!(parse_leetcode::<Vec<String>>(&formatted), Ok(values));
+        }
+
+        #[test]
+        fn parse_debug_output(values in vec(vec(any::<i64>(), 0..5), 0..5)) {
+            let input = format!("{:?}", values);
+            prop_assert_eq!(parse_leetcode::<Vec<Vec<i64>>>(&input), Ok(values));
+        }
+
+        #[test]
+        fn parse_quoted_strings(values in vec("[a-zA-Z0-9 ]*", 0..5)) {
+            let input = format!("{:?}", values);
+            prop_assert_eq!(parse_leetcode::<Vec<String>>(&input), Ok(values));
+        }
+    }
+}
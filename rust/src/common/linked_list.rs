@@ -1,4 +1,12 @@
-#[derive(PartialEq, Eq, Clone, Debug)]
+use std::fmt::Display;
+use std::str::FromStr;
+
+pub use crate::common::list_algorithms::{
+    merge_sort, merge_sorted, middle, reverse, reverse_k_group, rotate_right, split_at,
+    split_middle,
+};
+
+#[derive(Debug)]
 pub struct ListNode<T> {
     pub val: T,
     pub next: Option<Box<ListNode<T>>>,
@@ -16,6 +24,70 @@ impl<T> ListNode<T> {
     }
 }
 
+/// drop the list node by node. The derived drop glue recurses once per node,
+/// so it overflows the stack on lists with ~10^5 nodes
+pub fn drop_list<T>(mut list: Option<Box<ListNode<T>>>) {
+    while let Some(mut node) = list {
+        list = node.next.take();
+    }
+}
+
+// Clone and PartialEq are implemented without recursion,
+// so that they work on lists with millions of nodes
+
+impl<T: Clone> Clone for ListNode<T> {
+    fn clone(&self) -> Self {
+        let mut head = ListNode::new(self.val.clone());
+
+        let mut tail = &mut head;
+        let mut current = self.next.as_deref();
+        while let Some(node) = current {
+            tail = tail.next.insert(Box::new(ListNode::new(node.val.clone())));
+            current = node.next.as_deref();
+        }
+
+        head
+    }
+}
+
+impl<T: PartialEq> PartialEq for ListNode<T> {
+    fn eq(&self, other: &Self) -> bool {
+        let mut this = Some(self);
+        let mut that = Some(other);
+
+        loop {
+            match (this, that) {
+                (None, None) => return true,
+                (Some(this_node), Some(that_node)) => {
+                    if this_node.val != that_node.val {
+                        return false;
+                    }
+                    this = this_node.next.as_deref();
+                    that = that_node.next.as_deref();
+                }
+                _ => return false,
+            }
+        }
+    }
+}
+
+impl<T: Eq> Eq for ListNode<T> {}
+
+impl<T: Display> Display for ListNode<T> {
+    /// values joined with arrows, e.g. `1 -> 4 -> 5`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.val)?;
+
+        let mut current = self.next.as_deref();
+        while let Some(node) = current {
+            write!(f, " -> {}", node.val)?;
+            current = node.next.as_deref();
+        }
+
+        Ok(())
+    }
+}
+
 pub fn vec_to_list<T>(mut values: Vec<T>) -> Option<Box<ListNode<T>>> {
     let mut head = None;
 
@@ -27,23 +99,702 @@ pub fn vec_to_list<T>(mut values: Vec<T>) -> Option<Box<ListNode<T>>> {
 }
 
 pub fn list_to_vec<T>(list: Option<Box<ListNode<T>>>) -> Vec<T> {
-    let mut result = vec![];
+    list.into_values().collect()
+}
+
+/// iterator over references to the list values
+pub struct Iter<'a, T> {
+    next: Option<&'a ListNode<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.next?;
+        self.next = node.next.as_deref();
+        Some(&node.val)
+    }
+}
+
+/// iterator over mutable references to the list values
+pub struct IterMut<'a, T> {
+    next: Option<&'a mut ListNode<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.next.take()?;
+        self.next = node.next.as_deref_mut();
+        Some(&mut node.val)
+    }
+}
+
+/// iterator taking the values out of the list
+pub struct IntoIter<T> {
+    next: Option<Box<ListNode<T>>>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.next.take()?;
+        self.next = node.next;
+        Some(node.val)
+    }
+}
+
+impl<T> Drop for IntoIter<T> {
+    fn drop(&mut self) {
+        drop_list(self.next.take());
+    }
+}
+
+impl<T> ListNode<T> {
+    /// iterate over the values starting from this node
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { next: Some(self) }
+    }
+
+    /// iterate mutably over the values starting from this node
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut { next: Some(self) }
+    }
+}
+
+impl<T> IntoIterator for Box<ListNode<T>> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { next: Some(self) }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ListNode<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut ListNode<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Operations on a possibly empty list.
+///
+/// `Option` is a foreign type, so they can't be inherent methods, and `Option`'s own
+/// `iter` / `iter_mut` / `into_iter` would shadow the list ones, so iterators are named after values
+pub trait ListExt<T> {
+    /// iterate over the values
+    fn values(&self) -> Iter<'_, T>;
+    /// iterate mutably over the values
+    fn values_mut(&mut self) -> IterMut<'_, T>;
+    /// take the values out of the list
+    fn into_values(self) -> IntoIter<T>;
+    /// number of nodes in the list
+    fn len(&self) -> usize;
+    /// check if the list has no nodes
+    fn is_empty(&self) -> bool;
+    /// get the n-th value (counting from 0)
+    fn nth(&self, n: usize) -> Option<&T>;
+    /// link another list to the end of this one
+    fn append(&mut self, other: Self);
+}
+
+impl<T> ListExt<T> for Option<Box<ListNode<T>>> {
+    fn values(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.as_deref(),
+        }
+    }
+
+    fn values_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            next: self.as_deref_mut(),
+        }
+    }
+
+    fn into_values(self) -> IntoIter<T> {
+        IntoIter { next: self }
+    }
+
+    fn len(&self) -> usize {
+        self.values().count()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.is_none()
+    }
+
+    fn nth(&self, n: usize) -> Option<&T> {
+        self.values().nth(n)
+    }
+
+    fn append(&mut self, other: Self) {
+        let mut tail = self;
+        while let Some(node) = tail {
+            tail = &mut node.next;
+        }
+        *tail = other;
+    }
+}
 
-    let mut head = list;
-    while let Some(node) = head {
-        result.push(node.val);
-        head = node.next;
+/// Collecting values into a list.
+///
+/// `FromIterator` can't be implemented for `Option<Box<ListNode<T>>>` because of the orphan rule
+pub trait CollectList<T> {
+    fn collect_list(self) -> Option<Box<ListNode<T>>>;
+}
+
+impl<T, I: Iterator<Item = T>> CollectList<T> for I {
+    fn collect_list(self) -> Option<Box<ListNode<T>>> {
+        let mut head = None;
+        let mut tail = &mut head;
+        for val in self {
+            tail = &mut tail.insert(Box::new(ListNode::new(val))).next;
+        }
+        head
     }
+}
 
-    result
+/// Cursor for editing a list in place.
+///
+/// The cursor points at a link between two nodes: initially the one before the head,
+/// so the "next" node is the one right after the cursor
+pub struct ListCursor<'a, T> {
+    /// always `Some` between the calls, taken temporarily to move the cursor
+    link: Option<&'a mut Option<Box<ListNode<T>>>>,
+}
+
+impl<'a, T> ListCursor<'a, T> {
+    /// create a cursor pointing before the head of the list
+    pub fn new(list: &'a mut Option<Box<ListNode<T>>>) -> Self {
+        ListCursor { link: Some(list) }
+    }
+
+    fn link(&self) -> &Option<Box<ListNode<T>>> {
+        self.link.as_ref().unwrap()
+    }
+
+    fn link_mut(&mut self) -> &mut Option<Box<ListNode<T>>> {
+        self.link.as_mut().unwrap()
+    }
+
+    /// check if there are no nodes after the cursor
+    pub fn is_at_end(&self) -> bool {
+        self.link().is_none()
+    }
+
+    /// move the cursor past the next node. Returns false if there is no next node
+    pub fn move_next(&mut self) -> bool {
+        let link = self.link.take().unwrap();
+        if link.is_some() {
+            self.link = Some(&mut link.as_mut().unwrap().next);
+            true
+        } else {
+            self.link = Some(link);
+            false
+        }
+    }
+
+    /// get the value of the next node
+    pub fn peek(&self) -> Option<&T> {
+        self.peek_nth(0)
+    }
+
+    /// get the value of the n-th node after the cursor (counting from 0)
+    pub fn peek_nth(&self, n: usize) -> Option<&T> {
+        self.link().values().nth(n)
+    }
+
+    /// get the mutable value of the next node
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.link_mut().as_mut().map(|node| &mut node.val)
+    }
+
+    /// unlink the next node and return its value
+    pub fn remove_next(&mut self) -> Option<T> {
+        let link = self.link_mut();
+        let node = link.take()?;
+        *link = node.next;
+        Some(node.val)
+    }
+
+    /// insert a new node right after the cursor
+    pub fn insert_after(&mut self, val: T) {
+        let link = self.link_mut();
+        let rest = link.take();
+        *link = Some(Box::new(ListNode { val, next: rest }));
+    }
+
+    /// cut the list at the cursor, returning the nodes after it
+    pub fn split(&mut self) -> Option<Box<ListNode<T>>> {
+        self.link_mut().take()
+    }
+
+    /// insert another list right after the cursor
+    pub fn splice(&mut self, mut list: Option<Box<ListNode<T>>>) {
+        let link = self.link_mut();
+        let rest = link.take();
+        list.append(rest);
+        *link = list;
+    }
+}
+
+/// error which can be returned when parsing a list from leetcode format
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParseListError {
+    /// input isn't enclosed in square brackets
+    MissingBrackets,
+    /// token at a given index isn't a valid node value
+    InvalidValue { index: usize, token: String },
+    /// list at a given index of a list of lists can't be parsed
+    InvalidList {
+        index: usize,
+        error: Box<ParseListError>,
+    },
+    /// node at a given index of a list of nodes described by lists can't be parsed
+    InvalidNode {
+        index: usize,
+        error: Box<ParseListError>,
+    },
+    /// node is described by a wrong number of values
+    WrongLength { expected: usize, actual: usize },
+    /// token is neither `null` nor an index of a node in the list
+    InvalidIndex { token: String },
+    /// token at a given index is a value which has no node to attach to
+    DanglingValue { index: usize, token: String },
+}
+
+impl Display for ParseListError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseListError::MissingBrackets => {
+                write!(f, "list must be enclosed in square brackets")
+            }
+            ParseListError::InvalidValue { index, token } => {
+                write!(f, "invalid node value `{}` at index {}", token, index)
+            }
+            ParseListError::InvalidList { index, error } => {
+                write!(f, "invalid list at index {}: {}", index, error)
+            }
+            ParseListError::InvalidNode { index, error } => {
+                write!(f, "invalid node at index {}: {}", index, error)
+            }
+            ParseListError::WrongLength { expected, actual } => {
+                write!(f, "expected {} values, got {}", expected, actual)
+            }
+            ParseListError::InvalidIndex { token } => {
+                write!(f, "invalid node index `{}`", token)
+            }
+            ParseListError::DanglingValue { index, token } => {
+                write!(
+                    f,
+                    "value `{}` at index {} has no node to attach to",
+                    token, index
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseListError {}
+
+pub(crate) fn strip_brackets(input: &str) -> Result<&str, ParseListError> {
+    Ok(input
+        .trim()
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .ok_or(ParseListError::MissingBrackets)?
+        .trim())
+}
+
+/// parse a list from leetcode format, e.g. `[1,4,5]`
+pub fn parse_list<T: FromStr>(input: &str) -> Result<Option<Box<ListNode<T>>>, ParseListError> {
+    let inner = strip_brackets(input)?;
+    if inner.is_empty() {
+        return Ok(None);
+    }
+
+    let mut values = vec![];
+    for (index, token) in inner.split(',').map(str::trim).enumerate() {
+        let value = token
+            .parse::<T>()
+            .map_err(|_| ParseListError::InvalidValue {
+                index,
+                token: token.to_owned(),
+            })?;
+        values.push(value);
+    }
+
+    Ok(vec_to_list(values))
+}
+
+/// parse a list of lists from leetcode format, e.g. `[[1,4,5],[1,3,4],[2,6]]`
+pub fn parse_lists<T: FromStr>(
+    input: &str,
+) -> Result<Vec<Option<Box<ListNode<T>>>>, ParseListError> {
+    let mut lists = vec![];
+    for (index, group) in split_groups(input)?.into_iter().enumerate() {
+        let list = parse_list(group).map_err(|error| ParseListError::InvalidList {
+            index,
+            error: Box::new(error),
+        })?;
+        lists.push(list);
+    }
+
+    Ok(lists)
+}
+
+/// split a list of lists into the inner lists still enclosed in brackets.
+/// Errors for malformed inner lists are wrapped into `InvalidList`
+pub(crate) fn split_groups(input: &str) -> Result<Vec<&str>, ParseListError> {
+    let mut rest = strip_brackets(input)?;
+
+    let mut groups = vec![];
+    while !rest.is_empty() {
+        let malformed = ParseListError::InvalidList {
+            index: groups.len(),
+            error: Box::new(ParseListError::MissingBrackets),
+        };
+
+        let end = match rest.find(']') {
+            Some(end) if rest.starts_with('[') => end,
+            _ => return Err(malformed),
+        };
+        groups.push(&rest[..=end]);
+
+        rest = rest[end + 1..].trim_start();
+        if let Some(after_comma) = rest.strip_prefix(',') {
+            rest = after_comma.trim_start();
+            if rest.is_empty() {
+                return Err(malformed);
+            }
+        } else if !rest.is_empty() {
+            return Err(malformed);
+        }
+    }
+
+    Ok(groups)
+}
+
+/// format a list in leetcode format without spaces, e.g. `[1,4,5]`
+pub fn format_list<T: Display>(list: &Option<Box<ListNode<T>>>) -> String {
+    let mut values = vec![];
+
+    let mut current = list.as_deref();
+    while let Some(node) = current {
+        values.push(node.val.to_string());
+        current = node.next.as_deref();
+    }
+
+    format!("[{}]", values.join(","))
+}
+
+/// proptest strategies generating random lists
+#[cfg(test)]
+pub mod strategies {
+    use super::*;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use std::fmt::Debug;
+
+    /// lists with at most `max_len` nodes. Shrinks towards shorter lists and smaller values
+    pub fn arb_list<S>(
+        values: S,
+        max_len: usize,
+    ) -> impl Strategy<Value = Option<Box<ListNode<S::Value>>>>
+    where
+        S: Strategy,
+        S::Value: Debug,
+    {
+        vec(values, 0..=max_len).prop_map(vec_to_list)
+    }
+
+    /// lists sorted in non-decreasing order with at most `max_len` nodes
+    pub fn arb_sorted_list<S>(
+        values: S,
+        max_len: usize,
+    ) -> impl Strategy<Value = Option<Box<ListNode<S::Value>>>>
+    where
+        S: Strategy,
+        S::Value: Ord + Debug,
+    {
+        vec(values, 0..=max_len).prop_map(|mut values| {
+            values.sort();
+            vec_to_list(values)
+        })
+    }
+
+    /// at most `max_lists` sorted lists with at most `max_len` nodes each
+    /// (leetcode "merge k sorted lists" input)
+    pub fn arb_sorted_lists<S>(
+        values: S,
+        max_lists: usize,
+        max_len: usize,
+    ) -> impl Strategy<Value = Vec<Option<Box<ListNode<S::Value>>>>>
+    where
+        S: Strategy + Clone,
+        S::Value: Ord + Debug,
+    {
+        vec(arb_sorted_list(values, max_len), 0..=max_lists)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::common::linked_list::{list_to_vec, vec_to_list};
+    use crate::common::linked_list::{
+        drop_list, format_list, list_to_vec, parse_list, parse_lists, vec_to_list, CollectList,
+        ListCursor, ListExt, ParseListError,
+    };
+    use proptest::prelude::*;
     use proptest::{prop_assert_eq, proptest};
+    use rstest::rstest;
+
+    #[test]
+    fn long_lists_are_dropped_cloned_and_compared() {
+        let len = 1_000_000;
+        let list = vec_to_list((0..len).collect::<Vec<_>>());
+
+        let copy = list.clone();
+        assert!(copy == list);
+
+        let shorter = vec_to_list((0..len - 1).collect::<Vec<_>>());
+        assert!(shorter != list);
+
+        let mut changed = vec_to_list((0..len).collect::<Vec<_>>());
+        let mut tail = changed.as_mut().unwrap();
+        while tail.next.is_some() {
+            tail = tail.next.as_mut().unwrap();
+        }
+        tail.val = -1;
+        assert!(changed != list);
+
+        assert_eq!(list_to_vec(copy).len(), len as usize);
+        for list in [list, shorter, changed] {
+            drop_list(list);
+        }
+    }
+
+    #[rstest]
+    #[case("[1,4,5]", vec![1, 4, 5])]
+    #[case(" [ 1, -4 , 5 ] ", vec![1, -4, 5])]
+    #[case("[]", vec![])]
+    fn parse_leetcode_format(#[case] input: &str, #[case] expected: Vec<i32>) {
+        assert_eq!(list_to_vec(parse_list::<i32>(input).unwrap()), expected);
+    }
+
+    #[rstest]
+    #[case("1,2", ParseListError::MissingBrackets)]
+    #[case("[1,x]", ParseListError::InvalidValue { index: 1, token: "x".into() })]
+    #[case("[1,,2]", ParseListError::InvalidValue { index: 1, token: "".into() })]
+    fn parse_errors(#[case] input: &str, #[case] expected: ParseListError) {
+        assert_eq!(parse_list::<i32>(input), Err(expected));
+    }
+
+    #[rstest]
+    #[case("[[1,4,5],[1,3,4],[2,6]]", Ok(vec![vec![1, 4, 5], vec![1, 3, 4], vec![2, 6]]))]
+    #[case("[ [] , [1] ]", Ok(vec![vec![], vec![1]]))]
+    #[case("[[]]", Ok(vec![vec![]]))]
+    #[case("[]", Ok(vec![]))]
+    #[case(
+        "[[1],[x]]",
+        Err("invalid list at index 1: invalid node value `x` at index 0")
+    )]
+    #[case(
+        "[[1],2]",
+        Err("invalid list at index 1: list must be enclosed in square brackets")
+    )]
+    #[case(
+        "[[1][2]]",
+        Err("invalid list at index 0: list must be enclosed in square brackets")
+    )]
+    #[case(
+        "[[1],]",
+        Err("invalid list at index 0: list must be enclosed in square brackets")
+    )]
+    fn parse_list_of_lists(#[case] input: &str, #[case] expected: Result<Vec<Vec<i32>>, &str>) {
+        let parsed = parse_lists::<i32>(input)
+            .map(|lists| lists.into_iter().map(list_to_vec).collect::<Vec<_>>())
+            .map_err(|err| err.to_string());
+
+        assert_eq!(parsed, expected.map_err(str::to_owned));
+    }
+
+    #[test]
+    fn display_and_format() {
+        let list = vec_to_list(vec![1, 4, 5]);
+
+        assert_eq!(list.as_ref().unwrap().to_string(), "1 -> 4 -> 5");
+        assert_eq!(format_list(&list), "[1,4,5]");
+        assert_eq!(format_list::<i32>(&None), "[]");
+    }
+
+    #[test]
+    fn iteration() {
+        let mut list = (1..=4).collect_list();
+
+        assert_eq!(list.values().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(list.len(), 4);
+        assert_eq!(list.nth(2), Some(&3));
+        assert_eq!(list.nth(4), None);
+
+        for val in list.values_mut() {
+            *val *= 10;
+        }
+        let head = list.as_mut().unwrap();
+        assert_eq!(head.iter().sum::<i32>(), 100);
+        for val in head.as_mut() {
+            *val += 1;
+        }
+        assert_eq!(list_to_vec(list), vec![11, 21, 31, 41]);
+    }
+
+    #[test]
+    fn empty_list() {
+        let mut list = std::iter::empty::<i32>().collect_list();
+
+        assert!(list.is_empty());
+        assert_eq!(list.len(), 0);
+        assert_eq!(list.nth(0), None);
+        assert_eq!(list.values_mut().next(), None);
+
+        list.append(vec_to_list(vec![1]));
+        assert_eq!(format_list(&list), "[1]");
+    }
+
+    #[test]
+    fn long_list_iteration() {
+        let len = 1_000_000;
+        let mut list = (0..len).collect_list();
+        list.append((0..len).collect_list());
+
+        assert_eq!(list.len(), 2 * len);
+        assert_eq!(list.nth(len + 5), Some(&5));
+
+        let mut values = list.unwrap().into_iter();
+        assert_eq!(values.nth(len - 1), Some(len - 1));
+        // the rest is dropped without recursion
+    }
+
+    #[derive(Debug, Clone)]
+    enum CursorOp {
+        MoveNext,
+        RemoveNext,
+        InsertAfter(i32),
+        Splice(Vec<i32>),
+        Split,
+    }
+
+    fn cursor_ops() -> impl Strategy<Value = Vec<CursorOp>> {
+        proptest::collection::vec(
+            prop_oneof![
+                3 => Just(CursorOp::MoveNext),
+                1 => Just(CursorOp::RemoveNext),
+                1 => (-100..100).prop_map(CursorOp::InsertAfter),
+                1 => proptest::collection::vec(-100..100, 0..4).prop_map(CursorOp::Splice),
+                1 => Just(CursorOp::Split),
+            ],
+            0..30,
+        )
+    }
+
+    #[test]
+    fn cursor_edits_list() {
+        let mut list = (1..=5).collect_list();
+        let mut cursor = ListCursor::new(&mut list);
+
+        assert_eq!(cursor.peek(), Some(&1));
+        assert!(cursor.move_next());
+        assert_eq!(cursor.peek_nth(2), Some(&4));
+        assert_eq!(cursor.remove_next(), Some(2));
+        cursor.insert_after(20);
+        *cursor.peek_mut().unwrap() += 1;
+        assert!(cursor.move_next());
+        cursor.splice(vec_to_list(vec![7, 8]));
+        assert_eq!(cursor.peek_nth(2), Some(&3));
+
+        let rest = cursor.split();
+        assert!(cursor.is_at_end());
+        assert!(!cursor.move_next());
+        assert_eq!(cursor.remove_next(), None);
+
+        assert_eq!(format_list(&list), "[1,21]");
+        assert_eq!(format_list(&rest), "[7,8,3,4,5]");
+    }
 
     proptest! {
+        #[test]
+        fn cursor_matches_vec(values in proptest::collection::vec(-100..100, 0..10), ops in cursor_ops()) {
+            let mut list = vec_to_list(values.clone());
+            let mut expected = values;
+            let mut position = 0;
+
+            let mut cursor = ListCursor::new(&mut list);
+            for op in ops {
+                match op {
+                    CursorOp::MoveNext => {
+                        let moved = position < expected.len();
+                        prop_assert_eq!(cursor.move_next(), moved);
+                        position += moved as usize;
+                    }
+                    CursorOp::RemoveNext => {
+                        let removed = (position < expected.len()).then(|| expected.remove(position));
+                        prop_assert_eq!(cursor.remove_next(), removed);
+                    }
+                    CursorOp::InsertAfter(val) => {
+                        cursor.insert_after(val);
+                        expected.insert(position, val);
+                    }
+                    CursorOp::Splice(values) => {
+                        cursor.splice(vec_to_list(values.clone()));
+                        expected.splice(position..position, values);
+                    }
+                    CursorOp::Split => {
+                        let rest = expected.split_off(position);
+                        prop_assert_eq!(list_to_vec(cursor.split()), rest);
+                    }
+                }
+                prop_assert_eq!(cursor.peek(), expected.get(position));
+                prop_assert_eq!(cursor.is_at_end(), position == expected.len());
+            }
+
+            prop_assert_eq!(list_to_vec(list), expected);
+        }
+
+        #[test]
+        fn append_concatenates(
+            first in proptest::collection::vec(-100..100, 0..10),
+            second in proptest::collection::vec(-100..100, 0..10),
+        ) {
+            let mut list = vec_to_list(first.clone());
+            list.append(second.iter().copied().collect_list());
+
+            let expected: Vec<i32> = first.into_iter().chain(second).collect();
+            prop_assert_eq!(list.len(), expected.len());
+            prop_assert_eq!(list.into_values().collect::<Vec<_>>(), expected);
+        }
+
+        #[test]
+        fn format_parse(input in proptest::collection::vec(-100..100, 0..10)) {
+            let list = vec_to_list(input);
+            let parsed = parse_list::<i32>(&format_list(&list)).unwrap();
+
+            prop_assert_eq!(parsed, list);
+        }
+
         #[test]
         fn parse_dump(input in proptest::collection::vec(-100..100, 0..5)) {
             let parsed = vec_to_list::<i32>(input.clone());
@@ -52,4 +803,35 @@ mod tests {
             prop_assert_eq!(input, dumped);
         }
     }
+
+    mod strategies_tests {
+        use super::super::strategies::{arb_list, arb_sorted_list, arb_sorted_lists};
+        use super::super::*;
+        use proptest::{prop_assert, proptest};
+
+        proptest! {
+            #[test]
+            fn arb_list_respects_bounds(list in arb_list(0..10, 5)) {
+                prop_assert!(list.len() <= 5);
+                prop_assert!(list.values().all(|val| (0..10).contains(val)));
+            }
+
+            #[test]
+            fn arb_sorted_list_is_sorted(list in arb_sorted_list(-100..100, 20)) {
+                let values: Vec<i32> = list.into_values().collect();
+                prop_assert!(values.len() <= 20);
+                prop_assert!(values.windows(2).all(|pair| pair[0] <= pair[1]));
+            }
+
+            #[test]
+            fn arb_sorted_lists_are_sorted(lists in arb_sorted_lists(-100..100, 4, 10)) {
+                prop_assert!(lists.len() <= 4);
+                for list in lists {
+                    let values: Vec<i32> = list.into_values().collect();
+                    prop_assert!(values.len() <= 10);
+                    prop_assert!(values.windows(2).all(|pair| pair[0] <= pair[1]));
+                }
+            }
+        }
+    }
 }
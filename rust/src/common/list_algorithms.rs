@@ -0,0 +1,295 @@
+//! Standard list transforms, re-exported from `linked_list`. The ones taking the list by value
+//! relink the existing nodes instead of allocating new ones.
+
+use crate::common::linked_list::{ListCursor, ListExt, ListNode};
+
+/// two parts of a split list
+type ListHalves<T> = (Option<Box<ListNode<T>>>, Option<Box<ListNode<T>>>);
+
+/// reverse the list
+pub fn reverse<T>(list: Option<Box<ListNode<T>>>) -> Option<Box<ListNode<T>>> {
+    let mut reversed = None;
+
+    let mut current = list;
+    while let Some(mut node) = current {
+        current = node.next.take();
+        node.next = reversed;
+        reversed = Some(node);
+    }
+
+    reversed
+}
+
+/// cut the list into its first `index` nodes and the rest
+pub fn split_at<T>(mut list: Option<Box<ListNode<T>>>, index: usize) -> ListHalves<T> {
+    let mut cursor = ListCursor::new(&mut list);
+    for _ in 0..index {
+        cursor.move_next();
+    }
+    let rest = cursor.split();
+
+    (list, rest)
+}
+
+/// cut the list in two halves, so that the second one starts at the middle node
+/// (the second of the two middle nodes for an even length)
+pub fn split_middle<T>(list: Option<Box<ListNode<T>>>) -> ListHalves<T> {
+    let middle = list.len() / 2;
+    split_at(list, middle)
+}
+
+/// middle node of the list, found without modifying it
+/// (the second of the two middle nodes for an even length, as in `split_middle`)
+pub fn middle<T>(list: &Option<Box<ListNode<T>>>) -> Option<&ListNode<T>> {
+    let mut slow = list.as_deref();
+    let mut fast = list.as_deref();
+    while let Some(next) = fast.and_then(|node| node.next.as_deref()) {
+        slow = slow.and_then(|node| node.next.as_deref());
+        fast = next.next.as_deref();
+    }
+
+    slow
+}
+
+/// merge two sorted lists into a sorted one. Equal values of the left list go first
+pub fn merge_sorted<T: Ord>(
+    mut left: Option<Box<ListNode<T>>>,
+    mut right: Option<Box<ListNode<T>>>,
+) -> Option<Box<ListNode<T>>> {
+    let mut head = None;
+    let mut tail = &mut head;
+
+    while let (Some(left_node), Some(right_node)) = (&left, &right) {
+        let source = if right_node.val < left_node.val {
+            &mut right
+        } else {
+            &mut left
+        };
+
+        let mut node = source.take().unwrap();
+        *source = node.next.take();
+        tail = &mut tail.insert(node).next;
+    }
+    *tail = left.or(right);
+
+    head
+}
+
+/// stable merge sort of the list
+pub fn merge_sort<T: Ord>(list: Option<Box<ListNode<T>>>) -> Option<Box<ListNode<T>>> {
+    if list.as_ref().and_then(|node| node.next.as_ref()).is_none() {
+        return list;
+    }
+
+    let (first, second) = split_middle(list);
+    merge_sorted(merge_sort(first), merge_sort(second))
+}
+
+/// reverse every group of k consecutive nodes, leaving the last incomplete group as it is
+pub fn reverse_k_group<T>(list: Option<Box<ListNode<T>>>, k: usize) -> Option<Box<ListNode<T>>> {
+    let mut head = None;
+    let mut tail = &mut head;
+
+    let mut rest = list;
+    while k > 0 && rest.nth(k - 1).is_some() {
+        let (group, next_groups) = split_at(rest, k);
+        *tail = reverse(group);
+        while let Some(node) = tail {
+            tail = &mut node.next;
+        }
+        rest = next_groups;
+    }
+    *tail = rest;
+
+    head
+}
+
+/// rotate the list to the right by k places
+pub fn rotate_right<T>(list: Option<Box<ListNode<T>>>, k: usize) -> Option<Box<ListNode<T>>> {
+    let len = list.len();
+    let shift = if len == 0 { 0 } else { k % len };
+    if shift == 0 {
+        return list;
+    }
+
+    let (first, mut second) = split_at(list, len - shift);
+    second.append(first);
+    second
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::linked_list::{list_to_vec, vec_to_list};
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use rstest::rstest;
+    use std::cmp::Ordering;
+
+    /// value compared only by its key, so that the order of equal keys shows stability
+    #[derive(Debug, Clone, Copy)]
+    struct Keyed(i32, usize);
+
+    impl PartialEq for Keyed {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+    impl Eq for Keyed {}
+    impl PartialOrd for Keyed {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+    impl Ord for Keyed {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+
+    fn keyed(values: Vec<i32>) -> Vec<Keyed> {
+        values
+            .into_iter()
+            .enumerate()
+            .map(|(index, val)| Keyed(val, index))
+            .collect()
+    }
+
+    fn indices(values: Vec<Keyed>) -> Vec<usize> {
+        values.into_iter().map(|Keyed(_, index)| index).collect()
+    }
+
+    #[rstest]
+    #[case(vec![1, 2, 3, 4, 5], 2, vec![2, 1, 4, 3, 5])]
+    #[case(vec![1, 2, 3, 4, 5], 3, vec![3, 2, 1, 4, 5])]
+    #[case(vec![1, 2, 3, 4, 5], 1, vec![1, 2, 3, 4, 5])]
+    #[case(vec![1, 2, 3, 4, 5], 0, vec![1, 2, 3, 4, 5])]
+    #[case(vec![1, 2], 3, vec![1, 2])]
+    fn reverse_k_group_examples(
+        #[case] values: Vec<i32>,
+        #[case] k: usize,
+        #[case] expected: Vec<i32>,
+    ) {
+        assert_eq!(
+            list_to_vec(reverse_k_group(vec_to_list(values), k)),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case(vec![1, 2, 3, 4, 5], 2, vec![4, 5, 1, 2, 3])]
+    #[case(vec![0, 1, 2], 4, vec![2, 0, 1])]
+    #[case(vec![], 1, vec![])]
+    fn rotate_right_examples(
+        #[case] values: Vec<i32>,
+        #[case] k: usize,
+        #[case] expected: Vec<i32>,
+    ) {
+        assert_eq!(list_to_vec(rotate_right(vec_to_list(values), k)), expected);
+    }
+
+    #[rstest]
+    #[case(vec![], None)]
+    #[case(vec![1], Some(1))]
+    #[case(vec![1, 2], Some(2))]
+    #[case(vec![1, 2, 3, 4, 5], Some(3))]
+    #[case(vec![1, 2, 3, 4, 5, 6], Some(4))]
+    fn middle_examples(#[case] values: Vec<i32>, #[case] expected: Option<i32>) {
+        let list = vec_to_list(values);
+        assert_eq!(middle(&list).map(|node| node.val), expected);
+    }
+
+    #[test]
+    fn long_list() {
+        let len = 1_000_000;
+        let list = vec_to_list((0..len).rev().collect());
+
+        let sorted = merge_sort(list);
+        let reversed = reverse(sorted);
+        let rotated = rotate_right(reversed, 1);
+        let regrouped = reverse_k_group(rotated, 1000);
+
+        let values = list_to_vec(regrouped);
+        assert_eq!(values.len(), len);
+        // rotated list is [0, len - 1, len - 2, ..., 1], and its first group of 1000 is reversed
+        assert_eq!(values[..3], [len - 999, len - 998, len - 997]);
+        assert_eq!(values[999], 0);
+    }
+
+    proptest! {
+        #[test]
+        fn merge_sort_is_stable(values in vec(-10..10, 0..50)) {
+            let values = keyed(values);
+            let mut expected = values.clone();
+            expected.sort();
+
+            let sorted = list_to_vec(merge_sort(vec_to_list(values)));
+            prop_assert_eq!(indices(sorted), indices(expected));
+        }
+
+        #[test]
+        fn merge_sorted_matches_vec(mut left in vec(-10..10, 0..20), mut right in vec(-10..10, 0..20)) {
+            left.sort();
+            right.sort();
+            let left = keyed(left);
+            let right: Vec<Keyed> = right
+                .into_iter()
+                .enumerate()
+                .map(|(index, val)| Keyed(val, left.len() + index))
+                .collect();
+
+            let mut expected: Vec<Keyed> = left.iter().chain(right.iter()).copied().collect();
+            expected.sort();
+
+            let merged = merge_sorted(vec_to_list(left), vec_to_list(right));
+            prop_assert_eq!(indices(list_to_vec(merged)), indices(expected));
+        }
+
+        #[test]
+        fn reverse_matches_vec(values in vec(-100..100, 0..50)) {
+            let mut expected = values.clone();
+            expected.reverse();
+
+            prop_assert_eq!(list_to_vec(reverse(vec_to_list(values))), expected);
+        }
+
+        #[test]
+        fn reverse_k_group_matches_vec(values in vec(-100..100, 0..50), k in 1usize..8) {
+            let mut expected = values.clone();
+            let full_len = values.len() / k * k;
+            for chunk in expected[..full_len].chunks_mut(k) {
+                chunk.reverse();
+            }
+
+            prop_assert_eq!(list_to_vec(reverse_k_group(vec_to_list(values), k)), expected);
+        }
+
+        #[test]
+        fn rotate_right_matches_vec(values in vec(-100..100, 0..50), k in 0usize..100) {
+            let mut expected = values.clone();
+            if !expected.is_empty() {
+                let len = expected.len();
+                expected.rotate_right(k % len);
+            }
+
+            prop_assert_eq!(list_to_vec(rotate_right(vec_to_list(values), k)), expected);
+        }
+
+        #[test]
+        fn middle_matches_vec(values in vec(-100..100, 0..50)) {
+            let list = vec_to_list(values.clone());
+
+            prop_assert_eq!(middle(&list).map(|node| node.val), values.get(values.len() / 2).copied());
+            prop_assert_eq!(list_to_vec(list), values);
+        }
+
+        #[test]
+        fn split_middle_matches_vec(values in vec(-100..100, 0..50)) {
+            let (first, second) = split_middle(vec_to_list(values.clone()));
+            let (expected_first, expected_second) = values.split_at(values.len() / 2);
+
+            prop_assert_eq!(list_to_vec(first), expected_first);
+            prop_assert_eq!(list_to_vec(second), expected_second);
+        }
+    }
+}
@@ -1,77 +1,308 @@
+pub mod approx;
+pub mod arena_list;
+pub mod arena_tree;
+pub mod assert_helpers;
 pub mod binary_tree;
+pub mod bst;
+pub mod design_test;
 pub mod disjoint_sets_union;
+pub mod doubly_linked_list;
+pub mod grid;
+pub mod leetcode_format;
 pub mod linked_list;
+pub mod list_algorithms;
+pub mod random_list;
+pub mod tree_ancestors;
+pub mod tree_builders;
+pub mod tree_fold;
 pub mod trie;
 pub mod weighted_graph;
 
+/// `key: value` line of an assertion message, empty for literals. The value may be given
+/// as a reference to the already evaluated expression, so that it isn't evaluated again
 #[macro_export]
 macro_rules! format_expr_kv {
     ($key:literal) => {
         ""
     };
+    ($key:literal, $value:expr) => {
+        ""
+    };
+    ($key:expr, $value:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::common::assert_helpers::{
+            DebugRepr as _, ListRepr as _, SeqRepr as _, TreeRepr as _,
+        };
+
+        let repr = (&$crate::common::assert_helpers::AssertArg($value)).assert_repr();
+        $crate::common::assert_helpers::format_key_value(stringify!($key), &repr)
+    }};
     ($key:expr) => {
-        format!("\n  {}: `{}`", stringify!($key), format!("{:?}", $key))
+        $crate::format_expr_kv!($key, &$key)
     };
 }
 
 #[macro_export]
 macro_rules! assert_eq {
     ($left:expr, $right:expr, $fmt_str:literal, $($fmt_args:expr),*) => {
-        let left_lit = stringify!($left);
-        let right_lit = stringify!($right);
-
-        if ($left != $right) {
-            let custom_message = format!($fmt_str, $($fmt_args),*);
-            let appendix = if custom_message.is_empty() {
-                "".into()
-            } else {
-                format!("\nmessage: {}", custom_message)
-            };
-
-            let left_kv = $crate::format_expr_kv!($left);
-            let right_kv = $crate::format_expr_kv!($right);
-
-            let where_msg = if left_kv.is_empty() && right_kv.is_empty() {
-                ""
-            } else {
-                "\nwhere"
-            };
+        match (&$left, &$right) {
+            (left_val, right_val) => {
+                let left_lit = stringify!($left);
+                let right_lit = stringify!($right);
 
-            panic!(
-                "assertion failed:\n`{left_lit} == {right_lit}`{where_msg}{left_kv}{right_kv}{appendix}"
-            );
+                if (*left_val != *right_val) {
+                    let custom_message = format!($fmt_str, $($fmt_args),*);
+                    let appendix = if custom_message.is_empty() {
+                        "".into()
+                    } else {
+                        format!("\nmessage: {}", custom_message)
+                    };
+
+                    let left_kv = $crate::format_expr_kv!($left, left_val);
+                    let right_kv = $crate::format_expr_kv!($right, right_val);
+
+                    let where_msg = if left_kv.is_empty() && right_kv.is_empty() {
+                        ""
+                    } else {
+                        "\nwhere"
+                    };
+
+                    #[allow(unused_imports)]
+                    use $crate::common::assert_helpers::{
+                        MatrixDiffRepr as _, NoDiff as _, SeqDiffRepr as _, TreeDiffRepr as _,
+                    };
+
+                    let diff_msg = (&&$crate::common::assert_helpers::AssertPair(left_val, right_val))
+                        .assert_diff()
+                        .map(|diff| format!("\ndifference: {}", diff))
+                        .unwrap_or_default();
+
+                    panic!(
+                        "assertion failed:\n`{left_lit} == {right_lit}`{where_msg}{left_kv}{right_kv}{diff_msg}{appendix}"
+                    );
+                }
+            }
         }
     };
 
     ($left:expr, $right:expr) => {
-        assert_eq!($left, $right, "{}", "");
+        $crate::assert_eq!($left, $right, "{}", "");
     }
 }
+/// representation of a value in leetcode format, falling back to `Debug` for other types
+#[macro_export]
+macro_rules! format_leetcode_repr {
+    ($value:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::common::assert_helpers::{DebugRepr as _, LeetcodeRepr as _};
+
+        (&$crate::common::assert_helpers::AssertArg(&$value)).assert_repr()
+    }};
+}
+
 #[macro_export]
 macro_rules! assert_returns {
     ($ret_value:expr, $func:expr, $($args:expr),*) => {
         let mut args_str: String = "".into();
         $(
-            args_str += format!("{:?}, ", $args).as_str();
+            args_str += format!("{}, ", $crate::format_leetcode_repr!($args)).as_str();
         )*
         args_str.pop();
         args_str.pop();
 
         let result = $func($($args),*);
+        let expected = $ret_value;
+        let is_equal = result == expected;
 
         // `stringify!` will convert the expression *as it is* into a string.
         let error_msg = format!(
-            "{}({}) returned {:?}\nexpected result: {:?}\n",
+            "{}({}) returned {}\nexpected result: {}\n",
             stringify!($func),
             args_str,
-            result,
-            $ret_value,
+            $crate::format_leetcode_repr!(result),
+            $crate::format_leetcode_repr!(expected),
+            );
+
+        assert!(is_equal, "\n{:}", error_msg);
+    };
+}
+
+/// assert that floats (or nested vectors of floats) are equal within a tolerance, given as
+/// `abs = ...` or `rel = ...`. The default is the absolute tolerance of the leetcode judge.
+/// Reports the max deviation and where it occurred
+#[macro_export]
+macro_rules! assert_eq_approx {
+    ($left:expr, $right:expr, abs = $tolerance:expr) => {
+        $crate::assert_eq_approx!(@check $left, $right, $crate::common::approx::Tolerance::Absolute($tolerance));
+    };
+
+    ($left:expr, $right:expr, rel = $tolerance:expr) => {
+        $crate::assert_eq_approx!(@check $left, $right, $crate::common::approx::Tolerance::Relative($tolerance));
+    };
+
+    ($left:expr, $right:expr) => {
+        $crate::assert_eq_approx!($left, $right, abs = $crate::common::approx::LEETCODE_TOLERANCE);
+    };
+
+    (@check $left:expr, $right:expr, $tolerance:expr) => {
+        match (&$left, &$right) {
+            (left_val, right_val) => {
+                if let Err(error) =
+                    $crate::common::approx::check_approx(left_val, right_val, $tolerance)
+                {
+                    let left_kv = $crate::format_expr_kv!($left, left_val);
+                    let right_kv = $crate::format_expr_kv!($right, right_val);
+                    let where_msg = if left_kv.is_empty() && right_kv.is_empty() {
+                        ""
+                    } else {
+                        "\nwhere"
+                    };
+
+                    panic!(
+                        "assertion failed:\n`{} == {}` (approximately){}{}{}\ndifference: {}",
+                        stringify!($left),
+                        stringify!($right),
+                        where_msg,
+                        left_kv,
+                        right_kv,
+                        error
+                    );
+                }
+            }
+        }
+    };
+}
+
+/// `assert_returns!` for functions returning floats (or nested vectors of floats),
+/// with an optional `abs = ...` or `rel = ...` tolerance before the expected value
+#[macro_export]
+macro_rules! assert_returns_approx {
+    (abs = $tolerance:expr, $ret_value:expr, $func:expr, $($args:expr),*) => {
+        $crate::assert_returns_approx!(@check $crate::common::approx::Tolerance::Absolute($tolerance), $ret_value, $func, $($args),*);
+    };
+
+    (rel = $tolerance:expr, $ret_value:expr, $func:expr, $($args:expr),*) => {
+        $crate::assert_returns_approx!(@check $crate::common::approx::Tolerance::Relative($tolerance), $ret_value, $func, $($args),*);
+    };
+
+    (@check $tolerance:expr, $ret_value:expr, $func:expr, $($args:expr),*) => {
+        let mut args_str: String = "".into();
+        $(
+            args_str += format!("{}, ", $crate::format_leetcode_repr!($args)).as_str();
+        )*
+        args_str.pop();
+        args_str.pop();
+
+        let result = $func($($args),*);
+        let expected = $ret_value;
+
+        if let Err(error) = $crate::common::approx::check_approx(&result, &expected, $tolerance) {
+            panic!(
+                "\n{}({}) returned {}\nexpected result: {}\ndifference: {}\n",
+                stringify!($func),
+                args_str,
+                $crate::format_leetcode_repr!(result),
+                $crate::format_leetcode_repr!(expected),
+                error
             );
+        }
+    };
 
-        assert!(result == $ret_value, "\n{:}", error_msg);
+    ($ret_value:expr, $func:expr, $($args:expr),*) => {
+        $crate::assert_returns_approx!(abs = $crate::common::approx::LEETCODE_TOLERANCE, $ret_value, $func, $($args),*);
     };
 }
 
+/// assert that `$actual` and `$expected` have the same elements in any order,
+/// counting repeated elements. With `ignore_inner_order` the order of elements
+/// inside nested collections is ignored too. Reports missing and unexpected elements
+#[macro_export]
+macro_rules! assert_eq_unordered {
+    ($actual:expr, $expected:expr, ignore_inner_order) => {
+        let actual = $crate::common::assert_helpers::sort_inner($actual);
+        let expected = $crate::common::assert_helpers::sort_inner($expected);
+        $crate::assert_eq_unordered!(@compare actual, expected, $actual, $expected);
+    };
+
+    ($actual:expr, $expected:expr) => {
+        let actual: Vec<_> = IntoIterator::into_iter($actual).collect();
+        let expected: Vec<_> = IntoIterator::into_iter($expected).collect();
+        $crate::assert_eq_unordered!(@compare actual, expected, $actual, $expected);
+    };
+
+    (@compare $actual:ident, $expected:ident, $actual_expr:expr, $expected_expr:expr) => {
+        let (unexpected, missing) =
+            $crate::common::assert_helpers::unmatched(&$actual, &$expected);
+
+        if !unexpected.is_empty() || !missing.is_empty() {
+            let mut details = String::new();
+            if !missing.is_empty() {
+                let repr = $crate::format_leetcode_repr!(missing);
+                details += &$crate::common::assert_helpers::format_key_value("missing", &repr);
+            }
+            if !unexpected.is_empty() {
+                let repr = $crate::format_leetcode_repr!(unexpected);
+                details += &$crate::common::assert_helpers::format_key_value("unexpected", &repr);
+            }
+
+            panic!(
+                "assertion failed:\n`{} == {}` (ignoring order)\nwhere{}",
+                stringify!($actual_expr),
+                stringify!($expected_expr),
+                details
+            );
+        }
+    };
+}
+
+/// replay an example of a leetcode "design" problem, given as arrays of calls,
+/// call arguments and expected outputs.
+///
+/// The type lists the functions available to the example with their arguments, the first one
+/// being the constructor, e.g. `SnapshotArray { new(length), set(index, val), snap() }`.
+/// Method names are matched in camelCase, and a class named differently from the type
+/// is given as `WordFilter2 as "WordFilter"`. Panics at the first step with a wrong output
+#[macro_export]
+macro_rules! design_test {
+    (
+        $type:ident $(as $class:literal)? {
+            $constructor:ident($($constructor_arg:ident),* $(,)?)
+            $(, $method:ident($($arg:ident),* $(,)?))* $(,)?
+        },
+        $calls:expr, $args:expr, $expected:expr $(,)?
+    ) => {{
+        let steps = $crate::common::design_test::parse_steps($calls, $args, $expected);
+        let mut steps = steps.iter();
+
+        // the class name given with `as` goes last
+        let class = *[stringify!($type), $($class)?].last().unwrap();
+        let step = steps.next().expect("example has no calls");
+        step.expect_name(class);
+
+        let mut object = {
+            #[allow(unused_mut)]
+            let mut args = step.arguments();
+            $(let $constructor_arg = args.next_value();)*
+            args.finish();
+            $type::$constructor($($constructor_arg),*)
+        };
+
+        for step in steps {
+            $(
+                if step.name == $crate::common::design_test::camel_case(stringify!($method)) {
+                    #[allow(unused_mut)]
+                    let mut args = step.arguments();
+                    $(let $arg = args.next_value();)*
+                    args.finish();
+                    step.check($type::$method(&mut object, $($arg),*));
+                    continue;
+                }
+            )*
+            step.unknown_method();
+        }
+    }};
+}
+
 /// 2d vector literal macro
 #[macro_export]
 macro_rules! vec2d {
@@ -82,54 +313,68 @@ macro_rules! vec2d {
     };
 }
 
-pub fn parse_2d_array(input_str: &str) -> Vec<Vec<i32>> {
-    let mut result = vec![];
+/// build a `Grid` from rows written like `vec2d!`. Panics if the rows have different lengths
+#[macro_export]
+macro_rules! grid {
+    ($($rows:tt)*) => {
+        $crate::common::grid::Grid::try_from($crate::vec2d![$($rows)*])
+            .unwrap_or_else(|error| panic!("invalid grid: {}", error))
+    };
+}
 
-    let mut current_vec: Vec<i32> = vec![];
+/// binary tree literal macro, producing `NodeLink<T>`.
+///
+/// Accepts either leetcode level-order form with `null` for missing nodes, e.g. `tree![1, null, 2, 3]`,
+/// or nested `(value, left, right)` form with `_` for missing nodes, e.g. `tree!(5, (1, _, 3), (9, 7, 15))`.
+/// A leaf in the nested form must be a single token tree, so negative leaves are written as `(-1)`
+#[macro_export]
+macro_rules! tree {
+    (@node _) => { None };
+    (@node null) => { None };
+    (@node ($val:expr, $left:tt, $right:tt)) => {
+        $crate::common::binary_tree::TreeNode::new_link(
+            $val,
+            $crate::tree!(@node $left),
+            $crate::tree!(@node $right),
+        )
+    };
+    (@node $leaf:expr) => {
+        $crate::common::binary_tree::TreeNode::new_link($leaf, None, None)
+    };
 
-    let mut current_num = 0;
-    let mut is_num = false;
-    let mut is_arr = false;
-    for ch in input_str.chars().skip(1) {
-        match ch {
-            '[' => {
-                is_arr = true;
-            }
-            ']' => {
-                if is_arr {
-                    is_arr = false;
-                    if is_num {
-                        is_num = false;
-                        current_vec.push(current_num);
-                    }
-                    current_num = 0;
-                    result.push(std::mem::replace(&mut current_vec, vec![]));
-                } else {
-                    break;
-                }
-            }
-            ',' => {
-                if is_arr {
-                    is_num = false;
-                    current_vec.push(current_num);
-                    current_num = 0;
-                }
-            }
-            digit @ '0'..='9' => {
-                if !is_num {
-                    is_num = true;
-                    current_num = digit.to_digit(10).unwrap() as i32;
-                } else {
-                    current_num = current_num * 10 + digit.to_digit(10).unwrap() as i32;
-                }
-            }
-            ' ' => {}
-            _ => {
-                unreachable!()
-            }
-        }
-    }
-    result
+    () => { None };
+
+    // nested form: either child is a `(value, left, right)` group or `_`
+    ($val:expr, ($($left:tt)*), $right:tt $(,)?) => {
+        $crate::tree!(@node ($val, ($($left)*), $right))
+    };
+    ($val:expr, $left:tt, ($($right:tt)*) $(,)?) => {
+        $crate::tree!(@node ($val, $left, ($($right)*)))
+    };
+    ($val:expr, _, $right:tt $(,)?) => {
+        $crate::tree!(@node ($val, _, $right))
+    };
+    ($val:expr, $left:tt, _ $(,)?) => {
+        $crate::tree!(@node ($val, $left, _))
+    };
+
+    // level-order form
+    ($($item:expr),+ $(,)?) => {{
+        #[allow(unused_imports)]
+        use $crate::common::binary_tree::{NullSlot as _, ValueSlot as _};
+        #[allow(non_upper_case_globals, dead_code)]
+        const null: $crate::common::binary_tree::Null = $crate::common::binary_tree::Null;
+
+        $crate::common::binary_tree::from_level_order(vec![
+            $((&mut $crate::common::binary_tree::TreeSlot(Some($item))).take_slot()),+
+        ])
+    }};
+}
+
+/// parse a matrix of integers in leetcode format, e.g. `[[1,-2],[3]]`.
+/// Panics on invalid input; use `leetcode_format::parse_leetcode` to handle errors
+pub fn parse_2d_array(input_str: &str) -> Vec<Vec<i32>> {
+    leetcode_format::parse_leetcode(input_str).unwrap_or_else(|error| panic!("{}", error))
 }
 
 #[cfg(test)]
@@ -149,6 +394,73 @@ mod tests {
         }
     }
 
+    mod tree_tests {
+        use crate::common::binary_tree::{parse_tree, NodeLink, TreeNode};
+
+        #[test]
+        fn empty_tree() {
+            let tree: NodeLink<i32> = tree![];
+            assert!(tree.is_none());
+
+            let tree: NodeLink<i32> = tree![null];
+            assert!(tree.is_none());
+        }
+
+        #[test]
+        fn level_order() {
+            let expected = parse_tree::<i32>("[1,null,2,3]").unwrap();
+            assert_eq!(tree![1, null, 2, 3], expected);
+
+            let expected = parse_tree::<i32>("[-1,2,-3,null,null,4,null,null,5]").unwrap();
+            assert_eq!(tree![-1, 2, -3, null, null, 4, null, null, 5,], expected);
+        }
+
+        #[test]
+        fn level_order_of_expressions() {
+            let expected = TreeNode::new_link(
+                "b".to_string(),
+                None,
+                TreeNode::new_link("ab".to_string(), None, None),
+            );
+            assert_eq!(
+                tree!["b".to_string(), null, "a".to_string() + "b"],
+                expected
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "value at index 3 has no parent node")]
+        fn level_order_with_dangling_value() {
+            let _: NodeLink<i32> = tree![1, null, null, 2];
+        }
+
+        #[test]
+        fn nested() {
+            let expected = parse_tree::<i32>("[5,1,9,null,3,7,15]").unwrap();
+            assert_eq!(tree!(5, (1, _, 3), (9, 7, 15)), expected);
+
+            let expected = parse_tree::<i32>("[1,-2,null,null,3]").unwrap();
+            assert_eq!(tree!(1, (-2, _, 3), _), expected);
+
+            let expected = parse_tree::<i32>("[1,-2,3,null,null,null,-4]").unwrap();
+            assert_eq!(tree!(1, (-2), (3, _, (-4))), expected);
+
+            let expected = parse_tree::<i32>("[1,null,2,3]").unwrap();
+            assert_eq!(tree!(1, _, (2, 3, null)), expected);
+        }
+
+        #[test]
+        fn ambiguous_forms_give_same_tree() {
+            let expected = parse_tree::<i32>("[1,2,3]").unwrap();
+            assert_eq!(tree![1, 2, 3], expected);
+            assert_eq!(tree!(1, (2), 3), expected);
+
+            let expected = parse_tree::<i32>("[1,null,3]").unwrap();
+            assert_eq!(tree![1, null, 3], expected);
+            assert_eq!(tree!(1, _, 3), expected);
+        }
+    }
+
     mod parse_2d_array_tests {
         use crate::common::parse_2d_array;
 
@@ -175,5 +487,19 @@ mod tests {
             let parsed = parse_2d_array(input_str);
             assert_eq!(parsed, expected);
         }
+
+        #[test]
+        fn parser_works_on_negative_numbers() {
+            let input_str = "[[-1, 2], [3, -40]]";
+            let expected = vec2d![[-1, 2], [3, -40]];
+            let parsed = parse_2d_array(input_str);
+            assert_eq!(parsed, expected);
+        }
+
+        #[test]
+        #[should_panic(expected = "expected `,` or `]` at position 6, found `x`")]
+        fn parser_reports_position() {
+            parse_2d_array("[[1,2]x]");
+        }
     }
 }
@@ -0,0 +1,344 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::rc::{Rc, Weak};
+use std::str::FromStr;
+
+use crate::common::linked_list::{split_groups, strip_brackets, ParseListError};
+
+pub type RandomLink<T> = Option<Rc<RefCell<RandomListNode<T>>>>;
+
+/// Node of a list where every node may also point to an arbitrary node of the same list.
+///
+/// Random pointers are weak, so that they don't make reference cycles:
+/// nodes are owned by the `next` chain starting from the head
+#[derive(Debug)]
+pub struct RandomListNode<T> {
+    pub val: T,
+    pub next: RandomLink<T>,
+    pub random: Option<Weak<RefCell<RandomListNode<T>>>>,
+}
+
+impl<T> RandomListNode<T> {
+    #[inline]
+    pub fn new(val: T) -> Self {
+        RandomListNode {
+            val,
+            next: None,
+            random: None,
+        }
+    }
+}
+
+impl<T> Drop for RandomListNode<T> {
+    fn drop(&mut self) {
+        let mut next = self.next.take();
+        while let Some(node) = next {
+            // the rest of the list must stay attached to a node which is still referenced elsewhere
+            next = match Rc::try_unwrap(node) {
+                Ok(cell) => cell.into_inner().next.take(),
+                Err(_) => None,
+            };
+        }
+    }
+}
+
+/// nodes of the list in order
+fn nodes<T>(head: &RandomLink<T>) -> Vec<Rc<RefCell<RandomListNode<T>>>> {
+    std::iter::successors(head.clone(), |node| node.borrow().next.clone()).collect()
+}
+
+/// map every node to its position in the list
+fn positions<T>(
+    nodes: &[Rc<RefCell<RandomListNode<T>>>],
+) -> HashMap<*const RefCell<RandomListNode<T>>, usize> {
+    nodes
+        .iter()
+        .enumerate()
+        .map(|(index, node)| (Rc::as_ptr(node), index))
+        .collect()
+}
+
+/// position of the node the random pointer points to, or `None` if it's out of the list
+fn random_position<T>(
+    node: &Rc<RefCell<RandomListNode<T>>>,
+    positions: &HashMap<*const RefCell<RandomListNode<T>>, usize>,
+) -> Option<Option<usize>> {
+    match node.borrow().random.as_ref().and_then(Weak::upgrade) {
+        None => Some(None),
+        Some(target) => positions
+            .get(&Rc::as_ptr(&target))
+            .map(|&index| Some(index)),
+    }
+}
+
+/// parse a list from leetcode `[[val, random_index], ...]` format, e.g. `[[7,null],[13,0],[11,4]]`
+pub fn parse_random_list<T: FromStr>(input: &str) -> Result<RandomLink<T>, ParseListError> {
+    let groups = split_groups(input).map_err(|err| match err {
+        ParseListError::InvalidList { index, error } => {
+            ParseListError::InvalidNode { index, error }
+        }
+        err => err,
+    })?;
+    let invalid_node = |index, error| ParseListError::InvalidNode {
+        index,
+        error: Box::new(error),
+    };
+
+    let mut values = vec![];
+    let mut randoms = vec![];
+    for (index, group) in groups.iter().enumerate() {
+        let tokens: Vec<&str> = strip_brackets(group)?.split(',').map(str::trim).collect();
+        if tokens.len() != 2 {
+            let error = ParseListError::WrongLength {
+                expected: 2,
+                actual: tokens.len(),
+            };
+            return Err(invalid_node(index, error));
+        }
+
+        let val = tokens[0].parse::<T>().map_err(|_| {
+            let token = tokens[0].to_owned();
+            invalid_node(index, ParseListError::InvalidValue { index: 0, token })
+        })?;
+        let random = match tokens[1] {
+            "null" => None,
+            token => match token.parse::<usize>() {
+                Ok(random) if random < groups.len() => Some(random),
+                _ => {
+                    let token = token.to_owned();
+                    return Err(invalid_node(index, ParseListError::InvalidIndex { token }));
+                }
+            },
+        };
+
+        values.push(val);
+        randoms.push(random);
+    }
+
+    let nodes: Vec<_> = values
+        .into_iter()
+        .map(|val| Rc::new(RefCell::new(RandomListNode::new(val))))
+        .collect();
+    for (index, node) in nodes.iter().enumerate() {
+        let mut node_ref = node.borrow_mut();
+        node_ref.next = nodes.get(index + 1).cloned();
+        node_ref.random = randoms[index].map(|random| Rc::downgrade(&nodes[random]));
+    }
+
+    Ok(nodes.first().cloned())
+}
+
+/// format a list in leetcode `[[val, random_index], ...]` format without spaces.
+/// A random pointer to a node out of the list is formatted as `?`
+pub fn format_random_list<T: Display>(head: &RandomLink<T>) -> String {
+    let nodes = nodes(head);
+    let positions = positions(&nodes);
+
+    let items: Vec<String> = nodes
+        .iter()
+        .map(|node| {
+            let random = match random_position(node, &positions) {
+                Some(Some(index)) => index.to_string(),
+                Some(None) => "null".to_string(),
+                None => "?".to_string(),
+            };
+            format!("[{},{}]", node.borrow().val, random)
+        })
+        .collect();
+
+    format!("[{}]", items.join(","))
+}
+
+/// reason why a list isn't a deep copy of another one
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum DeepCopyError {
+    /// lists have different lengths
+    LengthMismatch { original: usize, copy: usize },
+    /// node at a given index has a different value
+    ValueMismatch { index: usize },
+    /// random pointer of the node at a given index points to a different position
+    /// or out of the copy
+    RandomMismatch { index: usize },
+    /// node at a given index of the copy belongs to the original list
+    SharedNode { index: usize },
+}
+
+impl Display for DeepCopyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeepCopyError::LengthMismatch { original, copy } => {
+                write!(f, "copy has {} nodes instead of {}", copy, original)
+            }
+            DeepCopyError::ValueMismatch { index } => {
+                write!(f, "value of node {} differs", index)
+            }
+            DeepCopyError::RandomMismatch { index } => {
+                write!(f, "random pointer of node {} differs", index)
+            }
+            DeepCopyError::SharedNode { index } => {
+                write!(f, "node {} of the copy belongs to the original", index)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DeepCopyError {}
+
+/// check that the copy has the same values and random pointers as the original,
+/// and shares no nodes with it
+pub fn check_deep_copy<T: PartialEq>(
+    original: &RandomLink<T>,
+    copy: &RandomLink<T>,
+) -> Result<(), DeepCopyError> {
+    let original_nodes = nodes(original);
+    let copy_nodes = nodes(copy);
+    if original_nodes.len() != copy_nodes.len() {
+        return Err(DeepCopyError::LengthMismatch {
+            original: original_nodes.len(),
+            copy: copy_nodes.len(),
+        });
+    }
+
+    let original_positions = positions(&original_nodes);
+    let copy_positions = positions(&copy_nodes);
+
+    for (index, (original_node, copy_node)) in original_nodes.iter().zip(&copy_nodes).enumerate() {
+        if original_positions.contains_key(&Rc::as_ptr(copy_node)) {
+            return Err(DeepCopyError::SharedNode { index });
+        }
+        if original_node.borrow().val != copy_node.borrow().val {
+            return Err(DeepCopyError::ValueMismatch { index });
+        }
+
+        let expected = random_position(original_node, &original_positions);
+        let actual = random_position(copy_node, &copy_positions);
+        if actual.is_none() || actual != expected {
+            return Err(DeepCopyError::RandomMismatch { index });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    /// copy the list node by node, keeping random pointers to the original nodes
+    fn shallow_copy(head: &RandomLink<i32>) -> RandomLink<i32> {
+        let nodes = nodes(head);
+        let copies: Vec<_> = nodes
+            .iter()
+            .map(|node| {
+                let node_ref = node.borrow();
+                let mut copy = RandomListNode::new(node_ref.val);
+                copy.random = node_ref.random.clone();
+                Rc::new(RefCell::new(copy))
+            })
+            .collect();
+        for (index, copy) in copies.iter().enumerate() {
+            copy.borrow_mut().next = copies.get(index + 1).cloned();
+        }
+        copies.first().cloned()
+    }
+
+    #[rstest]
+    #[case("[[7,null],[13,0],[11,4],[10,2],[1,0]]")]
+    #[case("[[1,1],[2,1]]")]
+    #[case("[[3,null],[3,0],[3,null]]")]
+    #[case("[]")]
+    fn parse_format(#[case] input: &str) {
+        let list = parse_random_list::<i32>(input).unwrap();
+        assert_eq!(format_random_list(&list), input);
+    }
+
+    #[rstest]
+    #[case("[[1,null],[2,2]]", "invalid node at index 1: invalid node index `2`")]
+    #[case("[[1,null],[2]]", "invalid node at index 1: expected 2 values, got 1")]
+    #[case(
+        "[[x,null]]",
+        "invalid node at index 0: invalid node value `x` at index 0"
+    )]
+    #[case(
+        "[[1,null],3]",
+        "invalid node at index 1: list must be enclosed in square brackets"
+    )]
+    #[case(
+        "[1,null]",
+        "invalid node at index 0: list must be enclosed in square brackets"
+    )]
+    fn parse_errors(#[case] input: &str, #[case] expected: &str) {
+        let error = parse_random_list::<i32>(input).unwrap_err();
+        assert_eq!(error.to_string(), expected);
+    }
+
+    #[test]
+    fn deep_copy_is_checked() {
+        let original = parse_random_list::<i32>("[[7,null],[13,0],[11,4],[10,2],[1,0]]").unwrap();
+
+        let copy = parse_random_list::<i32>("[[7,null],[13,0],[11,4],[10,2],[1,0]]").unwrap();
+        assert_eq!(check_deep_copy(&original, &copy), Ok(()));
+
+        let copy = parse_random_list::<i32>("[[7,null],[13,0],[11,3],[10,2]]").unwrap();
+        let expected = DeepCopyError::LengthMismatch {
+            original: 5,
+            copy: 4,
+        };
+        assert_eq!(check_deep_copy(&original, &copy), Err(expected));
+
+        let copy = parse_random_list::<i32>("[[7,null],[13,0],[11,4],[10,3],[1,0]]").unwrap();
+        let expected = DeepCopyError::RandomMismatch { index: 3 };
+        assert_eq!(check_deep_copy(&original, &copy), Err(expected));
+
+        let copy = parse_random_list::<i32>("[[7,null],[13,0],[12,4],[10,2],[1,0]]").unwrap();
+        let expected = DeepCopyError::ValueMismatch { index: 2 };
+        assert_eq!(check_deep_copy(&original, &copy), Err(expected));
+
+        let copy = shallow_copy(&original);
+        let expected = DeepCopyError::RandomMismatch { index: 1 };
+        assert_eq!(check_deep_copy(&original, &copy), Err(expected));
+        assert_eq!(
+            format_random_list(&copy),
+            "[[7,null],[13,?],[11,?],[10,?],[1,?]]"
+        );
+
+        let expected = DeepCopyError::SharedNode { index: 0 };
+        assert_eq!(check_deep_copy(&original, &original), Err(expected));
+    }
+
+    #[test]
+    fn long_list_is_dropped() {
+        let len = 1_000_000;
+        let input: Vec<String> = (0..len)
+            .map(|index| format!("[{},{}]", index, index / 2))
+            .collect();
+        let list = parse_random_list::<i32>(&format!("[{}]", input.join(","))).unwrap();
+
+        assert_eq!(nodes(&list).len(), len);
+    }
+
+    proptest! {
+        #[test]
+        fn format_parse(nodes in vec((-100..100, any::<Option<prop::sample::Index>>()), 0..20)) {
+            let len = nodes.len();
+            let items: Vec<String> = nodes
+                .into_iter()
+                .map(|(val, random)| match random {
+                    Some(random) => format!("[{},{}]", val, random.index(len)),
+                    None => format!("[{},null]", val),
+                })
+                .collect();
+            let input = format!("[{}]", items.join(","));
+
+            let list = parse_random_list::<i32>(&input).unwrap();
+            prop_assert_eq!(format_random_list(&list), input.clone());
+
+            let copy = parse_random_list::<i32>(&input).unwrap();
+            prop_assert_eq!(check_deep_copy(&list, &copy), Ok(()));
+        }
+    }
+}
@@ -0,0 +1,278 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::common::binary_tree::{preorder, NodeLink, TreeNode};
+
+pub type NodeID = usize;
+
+/// Ancestor queries over a binary tree using binary lifting.
+///
+/// Nodes get ids in pre-order during preprocessing (the root has id 0),
+/// so ids stay the same for the same tree shape. Preprocessing takes O(n log n),
+/// every query takes O(log n). Passing an id which doesn't belong to the tree panics
+pub struct TreeAncestors<T> {
+    nodes: Vec<Rc<RefCell<TreeNode<T>>>>,
+    ids: HashMap<*const RefCell<TreeNode<T>>, NodeID>,
+    depths: Vec<usize>,
+    /// `jumps[j][id]` is the 2^j-th ancestor of the node (or the root if there is no such one)
+    jumps: Vec<Vec<NodeID>>,
+}
+
+impl<T> TreeAncestors<T> {
+    /// preprocess the tree
+    pub fn new(root: &NodeLink<T>) -> Self {
+        let nodes: Vec<_> = preorder(root).collect();
+        let ids: HashMap<_, _> = nodes
+            .iter()
+            .enumerate()
+            .map(|(id, node)| (Rc::as_ptr(node), id))
+            .collect();
+
+        let mut parents: Vec<NodeID> = (0..nodes.len()).collect();
+        let mut depths: Vec<usize> = vec![0; nodes.len()];
+
+        // in pre-order every parent comes before its children
+        for (id, node) in nodes.iter().enumerate() {
+            let node_ref = node.borrow();
+            for child in node_ref.left.iter().chain(node_ref.right.iter()) {
+                let child_id = ids[&Rc::as_ptr(child)];
+                parents[child_id] = id;
+                depths[child_id] = depths[id] + 1;
+            }
+        }
+
+        let max_depth = depths.iter().max().cloned().unwrap_or(0);
+        let levels = (usize::BITS - max_depth.leading_zeros()).max(1) as usize;
+
+        let mut jumps = vec![parents];
+        for level in 1..levels {
+            let previous = &jumps[level - 1];
+            let next = previous
+                .iter()
+                .map(|&ancestor| previous[ancestor])
+                .collect();
+            jumps.push(next);
+        }
+
+        TreeAncestors {
+            nodes,
+            ids,
+            depths,
+            jumps,
+        }
+    }
+
+    /// number of nodes in the tree
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// check if the tree is empty
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// get the id of the node if it belongs to the tree
+    pub fn id_of(&self, node: &Rc<RefCell<TreeNode<T>>>) -> Option<NodeID> {
+        self.ids.get(&Rc::as_ptr(node)).cloned()
+    }
+
+    /// get the node by its id
+    pub fn node(&self, id: NodeID) -> Rc<RefCell<TreeNode<T>>> {
+        self.nodes[id].clone()
+    }
+
+    /// get the id of the node's parent. The root has no parent
+    pub fn parent(&self, id: NodeID) -> Option<NodeID> {
+        self.kth_ancestor(id, 1)
+    }
+
+    /// get the depth of the node (the root has depth 0)
+    pub fn depth(&self, id: NodeID) -> usize {
+        self.depths[id]
+    }
+
+    /// get the id of the node's ancestor k levels above it (the node itself for k = 0)
+    pub fn kth_ancestor(&self, id: NodeID, k: usize) -> Option<NodeID> {
+        if k > self.depths[id] {
+            return None;
+        }
+
+        let mut current = id;
+        for (level, jumps) in self.jumps.iter().enumerate() {
+            if k & (1 << level) != 0 {
+                current = jumps[current];
+            }
+        }
+
+        Some(current)
+    }
+
+    /// get the id of the lowest common ancestor of two nodes
+    pub fn lca(&self, id1: NodeID, id2: NodeID) -> NodeID {
+        let (mut deeper, mut other) = if self.depths[id1] >= self.depths[id2] {
+            (id1, id2)
+        } else {
+            (id2, id1)
+        };
+
+        deeper = self
+            .kth_ancestor(deeper, self.depths[deeper] - self.depths[other])
+            .unwrap();
+        if deeper == other {
+            return deeper;
+        }
+
+        for jumps in self.jumps.iter().rev() {
+            if jumps[deeper] != jumps[other] {
+                deeper = jumps[deeper];
+                other = jumps[other];
+            }
+        }
+
+        self.jumps[0][deeper]
+    }
+
+    /// get the number of edges on the path between two nodes
+    pub fn distance(&self, id1: NodeID, id2: NodeID) -> usize {
+        let ancestor = self.lca(id1, id2);
+        self.depths[id1] + self.depths[id2] - 2 * self.depths[ancestor]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::binary_tree::parse_tree;
+    use crate::common::binary_tree::strategies::arb_tree;
+    use proptest::prelude::*;
+    use rstest::{fixture, rstest};
+
+    /// find the parent by looking for a node which has the given one as a child
+    fn naive_parent<T>(ancestors: &TreeAncestors<T>, id: NodeID) -> Option<NodeID> {
+        let node = ancestors.node(id);
+        let is_node = |link: &NodeLink<T>| link.as_ref().is_some_and(|it| Rc::ptr_eq(it, &node));
+
+        (0..ancestors.len()).find(|&other| {
+            let other_node = ancestors.node(other);
+            let other_ref = other_node.borrow();
+            is_node(&other_ref.left) || is_node(&other_ref.right)
+        })
+    }
+
+    /// ancestors of the node from itself up to the root, found by walking parent links one by one
+    fn path_to_root<T>(ancestors: &TreeAncestors<T>, id: NodeID) -> Vec<NodeID> {
+        let mut path = vec![id];
+        while let Some(parent) = naive_parent(ancestors, *path.last().unwrap()) {
+            path.push(parent);
+        }
+        path
+    }
+
+    fn id_by_val(ancestors: &TreeAncestors<i32>, val: i32) -> NodeID {
+        (0..ancestors.len())
+            .find(|&id| ancestors.node(id).borrow().val == val)
+            .unwrap()
+    }
+
+    #[fixture]
+    fn leetcode_tree() -> NodeLink<i32> {
+        parse_tree("[3,5,1,6,2,0,8,null,null,7,4]").unwrap()
+    }
+
+    #[rstest]
+    #[case(5, 1, 3)]
+    #[case(5, 4, 5)]
+    #[case(6, 4, 5)]
+    #[case(7, 8, 3)]
+    #[case(7, 7, 7)]
+    fn lca_works(
+        leetcode_tree: NodeLink<i32>,
+        #[case] val1: i32,
+        #[case] val2: i32,
+        #[case] expected: i32,
+    ) {
+        let ancestors = TreeAncestors::new(&leetcode_tree);
+        let lca = ancestors.lca(id_by_val(&ancestors, val1), id_by_val(&ancestors, val2));
+
+        assert_eq!(ancestors.node(lca).borrow().val, expected);
+    }
+
+    #[rstest]
+    fn ids_follow_preorder(leetcode_tree: NodeLink<i32>) {
+        let ancestors = TreeAncestors::new(&leetcode_tree);
+
+        let values: Vec<i32> = (0..ancestors.len())
+            .map(|id| ancestors.node(id).borrow().val)
+            .collect();
+        assert_eq!(values, vec![3, 5, 6, 2, 7, 4, 1, 0, 8]);
+
+        let root = leetcode_tree.unwrap();
+        assert_eq!(ancestors.id_of(&root), Some(0));
+        assert_eq!(ancestors.id_of(&parse_tree("[3]").unwrap().unwrap()), None);
+    }
+
+    #[rstest]
+    fn distance_and_depth(leetcode_tree: NodeLink<i32>) {
+        let ancestors = TreeAncestors::new(&leetcode_tree);
+        let id = |val| id_by_val(&ancestors, val);
+
+        assert_eq!(ancestors.depth(id(3)), 0);
+        assert_eq!(ancestors.depth(id(4)), 3);
+        assert_eq!(ancestors.distance(id(7), id(8)), 5);
+        assert_eq!(ancestors.distance(id(6), id(5)), 1);
+        assert_eq!(ancestors.distance(id(0), id(0)), 0);
+    }
+
+    #[test]
+    fn empty_tree() {
+        let ancestors = TreeAncestors::<i32>::new(&None);
+        assert!(ancestors.is_empty());
+    }
+
+    #[test]
+    fn deep_tree() {
+        let depth = 100_000;
+        let mut root = None;
+        for val in (0..depth).rev() {
+            root = TreeNode::new_link(val, root, None);
+        }
+
+        let ancestors = TreeAncestors::new(&root);
+
+        assert_eq!(ancestors.kth_ancestor(depth - 1, depth - 1), Some(0));
+        assert_eq!(ancestors.kth_ancestor(depth - 1, depth), None);
+        assert_eq!(ancestors.kth_ancestor(70_000, 12_345), Some(57_655));
+        assert_eq!(ancestors.lca(99_999, 12_345), 12_345);
+        assert_eq!(ancestors.distance(99_999, 12_345), 87_654);
+    }
+
+    proptest! {
+        #[test]
+        fn queries_match_naive_walk(
+            tree in arb_tree(0..10, 60, 12),
+            id1 in any::<prop::sample::Index>(),
+            id2 in any::<prop::sample::Index>(),
+            k in 0usize..15,
+        ) {
+            let ancestors = TreeAncestors::new(&tree);
+            prop_assume!(!ancestors.is_empty());
+
+            let id1 = id1.index(ancestors.len());
+            let id2 = id2.index(ancestors.len());
+            let path1 = path_to_root(&ancestors, id1);
+            let path2 = path_to_root(&ancestors, id2);
+
+            prop_assert_eq!(ancestors.depth(id1), path1.len() - 1);
+            prop_assert_eq!(ancestors.parent(id1), path1.get(1).cloned());
+            prop_assert_eq!(ancestors.kth_ancestor(id1, k), path1.get(k).cloned());
+
+            let common = path1.iter().rev().zip(path2.iter().rev()).take_while(|(a, b)| a == b).count();
+            let expected_lca = path1[path1.len() - common];
+
+            prop_assert_eq!(ancestors.lca(id1, id2), expected_lca);
+            prop_assert_eq!(ancestors.distance(id1, id2), path1.len() + path2.len() - 2 * common);
+        }
+    }
+}
@@ -0,0 +1,558 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::rc::Rc;
+
+use crate::common::binary_tree::{NodeLink, TreeNode};
+
+/// error which can be returned when building a tree from an inconsistent description
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum BuildTreeError {
+    /// two sequences describing the same nodes have different lengths
+    LengthMismatch { left: usize, right: usize },
+    /// value at a given index of the second traversal repeats an earlier one
+    DuplicateValue { index: usize },
+    /// node at a given index of the preorder (postorder for an inorder/postorder pair)
+    /// can't be placed consistently with the other traversal
+    Inconsistent { index: usize },
+    /// node at a given index of the preorder has a single child, so the tree isn't full
+    NotFull { index: usize },
+    /// node at a given index refers to a parent which doesn't exist
+    UnknownParent { index: usize },
+    /// node at a given index takes a child slot of its parent which is already taken
+    SlotTaken { index: usize },
+    /// node described at a given index already has a parent
+    MultipleParents { index: usize },
+    /// every node has a parent
+    NoRoot,
+    /// node at a given index is a root, but there is a root already
+    MultipleRoots { index: usize },
+    /// node at a given index isn't connected to the root
+    Unreachable { index: usize },
+}
+
+impl Display for BuildTreeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuildTreeError::LengthMismatch { left, right } => {
+                write!(
+                    f,
+                    "sequences have different lengths: {} and {}",
+                    left, right
+                )
+            }
+            BuildTreeError::DuplicateValue { index } => {
+                write!(f, "value at index {} is duplicated", index)
+            }
+            BuildTreeError::Inconsistent { index } => {
+                write!(
+                    f,
+                    "node at index {} doesn't match the other traversal",
+                    index
+                )
+            }
+            BuildTreeError::NotFull { index } => {
+                write!(f, "node at index {} has a single child", index)
+            }
+            BuildTreeError::UnknownParent { index } => {
+                write!(f, "node at index {} has an unknown parent", index)
+            }
+            BuildTreeError::SlotTaken { index } => {
+                write!(f, "node at index {} takes an occupied child slot", index)
+            }
+            BuildTreeError::MultipleParents { index } => {
+                write!(f, "node at index {} already has a parent", index)
+            }
+            BuildTreeError::NoRoot => write!(f, "tree has no root"),
+            BuildTreeError::MultipleRoots { index } => {
+                write!(f, "node at index {} is a second root", index)
+            }
+            BuildTreeError::Unreachable { index } => {
+                write!(f, "node at index {} isn't connected to the root", index)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BuildTreeError {}
+
+/// place to attach a subtree to: a parent paired with a flag telling if it's the left child,
+/// or the root of the tree
+type Slot<T> = Option<(Rc<RefCell<TreeNode<T>>>, bool)>;
+
+fn attach<T>(root: &mut NodeLink<T>, slot: Slot<T>, node: Rc<RefCell<TreeNode<T>>>) {
+    match slot {
+        None => *root = Some(node),
+        Some((parent, true)) => parent.borrow_mut().left = Some(node),
+        Some((parent, false)) => parent.borrow_mut().right = Some(node),
+    }
+}
+
+fn new_node<T: Clone>(val: &T) -> Rc<RefCell<TreeNode<T>>> {
+    Rc::new(RefCell::new(TreeNode::new(val.clone())))
+}
+
+fn check_lengths(left: usize, right: usize) -> Result<(), BuildTreeError> {
+    if left != right {
+        return Err(BuildTreeError::LengthMismatch { left, right });
+    }
+    Ok(())
+}
+
+/// map every value to its index, failing on duplicates
+fn positions<T: Hash + Eq>(values: &[T]) -> Result<HashMap<&T, usize>, BuildTreeError> {
+    let mut result = HashMap::with_capacity(values.len());
+    for (index, val) in values.iter().enumerate() {
+        if result.insert(val, index).is_some() {
+            return Err(BuildTreeError::DuplicateValue { index });
+        }
+    }
+    Ok(result)
+}
+
+/// build a tree with distinct values from its preorder and inorder traversals
+pub fn from_preorder_inorder<T: Hash + Eq + Clone>(
+    preorder: &[T],
+    inorder: &[T],
+) -> Result<NodeLink<T>, BuildTreeError> {
+    check_lengths(preorder.len(), inorder.len())?;
+    let inorder_positions = positions(inorder)?;
+
+    let mut root = None;
+    // (start of the subtree in preorder, start in inorder, size, where to attach it)
+    let mut stack: Vec<(usize, usize, usize, Slot<T>)> = vec![];
+    if !preorder.is_empty() {
+        stack.push((0, 0, preorder.len(), None));
+    }
+
+    while let Some((pre_start, in_start, size, slot)) = stack.pop() {
+        let val = &preorder[pre_start];
+        let position = match inorder_positions.get(val) {
+            Some(&position) if (in_start..in_start + size).contains(&position) => position,
+            _ => return Err(BuildTreeError::Inconsistent { index: pre_start }),
+        };
+
+        let node = new_node(val);
+        let left_size = position - in_start;
+        let right_size = size - left_size - 1;
+
+        if right_size > 0 {
+            let slot = Some((node.clone(), false));
+            stack.push((pre_start + left_size + 1, position + 1, right_size, slot));
+        }
+        if left_size > 0 {
+            let slot = Some((node.clone(), true));
+            stack.push((pre_start + 1, in_start, left_size, slot));
+        }
+        attach(&mut root, slot, node);
+    }
+
+    Ok(root)
+}
+
+/// build a tree with distinct values from its inorder and postorder traversals
+pub fn from_inorder_postorder<T: Hash + Eq + Clone>(
+    inorder: &[T],
+    postorder: &[T],
+) -> Result<NodeLink<T>, BuildTreeError> {
+    check_lengths(inorder.len(), postorder.len())?;
+    let inorder_positions = positions(inorder)?;
+
+    let mut root = None;
+    // (start of the subtree in postorder, start in inorder, size, where to attach it)
+    let mut stack: Vec<(usize, usize, usize, Slot<T>)> = vec![];
+    if !postorder.is_empty() {
+        stack.push((0, 0, postorder.len(), None));
+    }
+
+    while let Some((post_start, in_start, size, slot)) = stack.pop() {
+        let index = post_start + size - 1;
+        let val = &postorder[index];
+        let position = match inorder_positions.get(val) {
+            Some(&position) if (in_start..in_start + size).contains(&position) => position,
+            _ => return Err(BuildTreeError::Inconsistent { index }),
+        };
+
+        let node = new_node(val);
+        let left_size = position - in_start;
+        let right_size = size - left_size - 1;
+
+        if right_size > 0 {
+            let slot = Some((node.clone(), false));
+            stack.push((post_start + left_size, position + 1, right_size, slot));
+        }
+        if left_size > 0 {
+            let slot = Some((node.clone(), true));
+            stack.push((post_start, in_start, left_size, slot));
+        }
+        attach(&mut root, slot, node);
+    }
+
+    Ok(root)
+}
+
+/// build a full tree (every node has 0 or 2 children) with distinct values
+/// from its preorder and postorder traversals
+pub fn from_preorder_postorder<T: Hash + Eq + Clone>(
+    preorder: &[T],
+    postorder: &[T],
+) -> Result<NodeLink<T>, BuildTreeError> {
+    check_lengths(preorder.len(), postorder.len())?;
+    let postorder_positions = positions(postorder)?;
+
+    let mut root = None;
+    // (start of the subtree in preorder, start in postorder, size, where to attach it)
+    let mut stack: Vec<(usize, usize, usize, Slot<T>)> = vec![];
+    if !preorder.is_empty() {
+        stack.push((0, 0, preorder.len(), None));
+    }
+
+    while let Some((pre_start, post_start, size, slot)) = stack.pop() {
+        let val = &preorder[pre_start];
+        if *val != postorder[post_start + size - 1] {
+            return Err(BuildTreeError::Inconsistent { index: pre_start });
+        }
+
+        let node = new_node(val);
+        if size > 1 {
+            // the left child comes right after the node in preorder and ends its subtree in postorder
+            let left_end = match postorder_positions.get(&preorder[pre_start + 1]) {
+                Some(&position) if (post_start..post_start + size - 1).contains(&position) => {
+                    position
+                }
+                _ => {
+                    return Err(BuildTreeError::Inconsistent {
+                        index: pre_start + 1,
+                    })
+                }
+            };
+
+            let left_size = left_end - post_start + 1;
+            let right_size = size - left_size - 1;
+            if right_size == 0 {
+                return Err(BuildTreeError::NotFull { index: pre_start });
+            }
+
+            let slot = Some((node.clone(), false));
+            stack.push((pre_start + left_size + 1, left_end + 1, right_size, slot));
+            let slot = Some((node.clone(), true));
+            stack.push((pre_start + 1, post_start, left_size, slot));
+        }
+        attach(&mut root, slot, node);
+    }
+
+    Ok(root)
+}
+
+/// build a tree from node values and their parents given as
+/// (index of the parent, is it the left child) or `None` for the root
+pub fn from_parent_array<T>(
+    values: Vec<T>,
+    parents: &[Option<(usize, bool)>],
+) -> Result<NodeLink<T>, BuildTreeError> {
+    check_lengths(values.len(), parents.len())?;
+    if values.is_empty() {
+        return Ok(None);
+    }
+
+    let mut root = None;
+    let mut lefts: Vec<Option<usize>> = vec![None; parents.len()];
+    let mut rights: Vec<Option<usize>> = vec![None; parents.len()];
+
+    for (index, &parent) in parents.iter().enumerate() {
+        let Some((parent, is_left)) = parent else {
+            if root.replace(index).is_some() {
+                return Err(BuildTreeError::MultipleRoots { index });
+            }
+            continue;
+        };
+
+        if parent >= parents.len() {
+            return Err(BuildTreeError::UnknownParent { index });
+        }
+        let child = if is_left {
+            &mut lefts[parent]
+        } else {
+            &mut rights[parent]
+        };
+        if child.replace(index).is_some() {
+            return Err(BuildTreeError::SlotTaken { index });
+        }
+    }
+
+    let root = root.ok_or(BuildTreeError::NoRoot)?;
+
+    // nodes are linked only after the check, so that cycles don't leak
+    let mut reachable = vec![false; parents.len()];
+    let mut stack = vec![root];
+    while let Some(index) = stack.pop() {
+        reachable[index] = true;
+        stack.extend(lefts[index]);
+        stack.extend(rights[index]);
+    }
+    if let Some(index) = reachable.iter().position(|&reached| !reached) {
+        return Err(BuildTreeError::Unreachable { index });
+    }
+
+    let nodes: Vec<Rc<RefCell<TreeNode<T>>>> = values
+        .into_iter()
+        .map(|val| Rc::new(RefCell::new(TreeNode::new(val))))
+        .collect();
+    for (index, node) in nodes.iter().enumerate() {
+        let mut node_ref = node.borrow_mut();
+        node_ref.left = lefts[index].map(|child| nodes[child].clone());
+        node_ref.right = rights[index].map(|child| nodes[child].clone());
+    }
+
+    Ok(Some(nodes[root].clone()))
+}
+
+/// build a tree with distinct values from edges given as (parent, child, is it the left child).
+/// Error indices refer to the descriptions
+pub fn from_descriptions<T: Hash + Eq + Clone>(
+    descriptions: &[(T, T, bool)],
+) -> Result<NodeLink<T>, BuildTreeError> {
+    let mut ids: HashMap<&T, usize> = HashMap::new();
+    let mut values = vec![];
+    let mut parents: Vec<Option<(usize, bool)>> = vec![];
+    // index of the description which mentions the node as a child (or at all for the root)
+    let mut described_at: Vec<usize> = vec![];
+
+    for (index, (parent, child, is_left)) in descriptions.iter().enumerate() {
+        let mut id_of = |val| {
+            *ids.entry(val).or_insert_with(|| {
+                values.push(val.clone());
+                parents.push(None);
+                described_at.push(index);
+                values.len() - 1
+            })
+        };
+        let parent_id = id_of(parent);
+        let child_id = id_of(child);
+
+        if parents[child_id].replace((parent_id, *is_left)).is_some() {
+            return Err(BuildTreeError::MultipleParents { index });
+        }
+        described_at[child_id] = index;
+    }
+
+    from_parent_array(values, &parents).map_err(|err| match err {
+        BuildTreeError::SlotTaken { index } => BuildTreeError::SlotTaken {
+            index: described_at[index],
+        },
+        BuildTreeError::MultipleRoots { index } => BuildTreeError::MultipleRoots {
+            index: described_at[index],
+        },
+        BuildTreeError::Unreachable { index } => BuildTreeError::Unreachable {
+            index: described_at[index],
+        },
+        err => err,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::arena_tree::ArenaTree;
+    use crate::common::binary_tree::strategies::arb_tree;
+    use crate::common::binary_tree::{format_tree, inorder, postorder, preorder};
+    use crate::common::tree_fold::map_values;
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    fn values(iter: impl Iterator<Item = Rc<RefCell<TreeNode<i32>>>>) -> Vec<i32> {
+        iter.map(|node| node.borrow().val).collect()
+    }
+
+    /// copy of the tree with values replaced by their pre-order indices
+    fn with_distinct_values(tree: &NodeLink<i32>) -> NodeLink<i32> {
+        let mut counter = 0;
+        map_values(tree, |_| {
+            counter += 1;
+            counter
+        })
+    }
+
+    /// add a leaf to every node which has a single child
+    fn make_full(tree: &NodeLink<i32>) {
+        for node in preorder(tree) {
+            let mut node_ref = node.borrow_mut();
+            match (node_ref.left.is_some(), node_ref.right.is_some()) {
+                (true, false) => node_ref.right = TreeNode::new_link(0, None, None),
+                (false, true) => node_ref.left = TreeNode::new_link(0, None, None),
+                _ => {}
+            }
+        }
+    }
+
+    #[rstest]
+    #[case(&[3, 9, 20, 15, 7], &[9, 3, 15, 20, 7], "[3,9,20,null,null,15,7]")]
+    #[case(&[1, 2, 3], &[3, 2, 1], "[1,2,null,3]")]
+    #[case(&[1, 2, 3], &[1, 2, 3], "[1,null,2,null,3]")]
+    #[case(&[], &[], "[]")]
+    fn preorder_inorder(#[case] pre: &[i32], #[case] ino: &[i32], #[case] expected: &str) {
+        let tree = from_preorder_inorder(pre, ino).unwrap();
+        assert_eq!(format_tree(&tree), expected);
+    }
+
+    #[rstest]
+    #[case(&[1, 2], &[1], BuildTreeError::LengthMismatch { left: 2, right: 1 })]
+    #[case(&[1, 2], &[2, 2], BuildTreeError::DuplicateValue { index: 1 })]
+    #[case(&[1, 2], &[1, 3], BuildTreeError::Inconsistent { index: 1 })]
+    #[case(&[1, 2, 3], &[3, 1, 2], BuildTreeError::Inconsistent { index: 1 })]
+    fn preorder_inorder_errors(
+        #[case] pre: &[i32],
+        #[case] ino: &[i32],
+        #[case] expected: BuildTreeError,
+    ) {
+        assert_eq!(from_preorder_inorder(pre, ino), Err(expected));
+    }
+
+    #[rstest]
+    #[case(&[9, 3, 15, 20, 7], &[9, 15, 7, 20, 3], Ok("[3,9,20,null,null,15,7]"))]
+    #[case(&[2, 1], &[2, 1], Ok("[1,2]"))]
+    #[case(&[2, 1], &[1, 2], Ok("[2,null,1]"))]
+    #[case(&[1, 2, 3], &[3, 2, 4], Err(BuildTreeError::Inconsistent { index: 2 }))]
+    #[case(&[1, 2, 3], &[2, 3, 1], Ok("[1,null,3,2]"))]
+    #[case(&[1, 2, 3], &[3, 1, 2], Err(BuildTreeError::Inconsistent { index: 0 }))]
+    fn inorder_postorder(
+        #[case] ino: &[i32],
+        #[case] post: &[i32],
+        #[case] expected: Result<&str, BuildTreeError>,
+    ) {
+        let tree = from_inorder_postorder(ino, post);
+        assert_eq!(
+            tree.map(|tree| format_tree(&tree)),
+            expected.map(str::to_owned)
+        );
+    }
+
+    #[rstest]
+    #[case(&[1, 2, 4, 5, 3, 6, 7], &[4, 5, 2, 6, 7, 3, 1], Ok("[1,2,3,4,5,6,7]"))]
+    #[case(&[1], &[1], Ok("[1]"))]
+    #[case(&[1, 2], &[2, 1], Err(BuildTreeError::NotFull { index: 0 }))]
+    #[case(&[1, 2, 3], &[2, 3, 4], Err(BuildTreeError::Inconsistent { index: 0 }))]
+    #[case(&[1, 2, 3], &[3, 2, 1], Err(BuildTreeError::NotFull { index: 0 }))]
+    #[case(&[1, 4, 3], &[2, 3, 1], Err(BuildTreeError::Inconsistent { index: 1 }))]
+    fn preorder_postorder(
+        #[case] pre: &[i32],
+        #[case] post: &[i32],
+        #[case] expected: Result<&str, BuildTreeError>,
+    ) {
+        let tree = from_preorder_postorder(pre, post);
+        assert_eq!(
+            tree.map(|tree| format_tree(&tree)),
+            expected.map(str::to_owned)
+        );
+    }
+
+    #[rstest]
+    #[case(&[None, Some((0, true)), Some((0, false))], Ok("[1,2,3]"))]
+    #[case(&[Some((2, false)), Some((0, true)), None], Ok("[3,null,1,2]"))]
+    #[case(&[None, Some((5, true)), None], Err(BuildTreeError::UnknownParent { index: 1 }))]
+    #[case(&[None, Some((0, true)), Some((0, true))], Err(BuildTreeError::SlotTaken { index: 2 }))]
+    #[case(&[None, None, Some((0, true))], Err(BuildTreeError::MultipleRoots { index: 1 }))]
+    #[case(&[Some((1, true)), Some((0, true)), Some((0, false))], Err(BuildTreeError::NoRoot))]
+    #[case(&[None, Some((2, true)), Some((1, true))], Err(BuildTreeError::Unreachable { index: 1 }))]
+    fn parent_array(
+        #[case] parents: &[Option<(usize, bool)>],
+        #[case] expected: Result<&str, BuildTreeError>,
+    ) {
+        let tree = from_parent_array(vec![1, 2, 3], parents);
+        assert_eq!(
+            tree.map(|tree| format_tree(&tree)),
+            expected.map(str::to_owned)
+        );
+    }
+
+    #[rstest]
+    #[case(
+        &[(20, 15, true), (20, 17, false), (50, 20, true), (50, 80, false), (80, 19, true)],
+        Ok("[50,20,80,15,17,19]")
+    )]
+    #[case(&[(1, 2, true), (2, 3, false), (2, 4, true)], Ok("[1,2,null,4,3]"))]
+    #[case(&[(1, 2, true), (3, 2, false)], Err(BuildTreeError::MultipleParents { index: 1 }))]
+    #[case(&[(1, 2, true), (1, 3, true)], Err(BuildTreeError::SlotTaken { index: 1 }))]
+    #[case(&[(1, 2, true), (3, 4, true)], Err(BuildTreeError::MultipleRoots { index: 1 }))]
+    #[case(&[(1, 2, true), (2, 1, true)], Err(BuildTreeError::NoRoot))]
+    #[case(
+        &[(1, 2, true), (3, 4, true), (4, 3, false)],
+        Err(BuildTreeError::Unreachable { index: 2 })
+    )]
+    fn edge_list(
+        #[case] descriptions: &[(i32, i32, bool)],
+        #[case] expected: Result<&str, BuildTreeError>,
+    ) {
+        let tree = from_descriptions(descriptions);
+        assert_eq!(
+            tree.map(|tree| format_tree(&tree)),
+            expected.map(str::to_owned)
+        );
+    }
+
+    #[test]
+    fn deep_tree() {
+        let depth = 100_000;
+        let pre: Vec<i32> = (0..depth).collect();
+        let ino: Vec<i32> = (0..depth).rev().collect();
+
+        let tree = from_preorder_inorder(&pre, &ino).unwrap();
+        assert_eq!(values(postorder(&tree)), ino);
+        assert!(from_inorder_postorder(&ino, &ino).unwrap() == tree);
+    }
+
+    proptest! {
+        #[test]
+        fn traversals_rebuild_tree(tree in arb_tree(0..1, 60, 12)) {
+            let tree = with_distinct_values(&tree);
+            let pre = values(preorder(&tree));
+            let ino = values(inorder(&tree));
+            let post = values(postorder(&tree));
+
+            prop_assert_eq!(from_preorder_inorder(&pre, &ino).unwrap(), tree.clone());
+            prop_assert_eq!(from_inorder_postorder(&ino, &post).unwrap(), tree);
+        }
+
+        #[test]
+        fn full_tree_is_rebuilt_from_preorder_postorder(tree in arb_tree(0..1, 60, 12)) {
+            make_full(&tree);
+            let tree = with_distinct_values(&tree);
+            let pre = values(preorder(&tree));
+            let post = values(postorder(&tree));
+
+            prop_assert_eq!(from_preorder_postorder(&pre, &post).unwrap(), tree);
+        }
+
+        #[test]
+        fn parent_array_rebuilds_tree(tree in arb_tree(-100..100, 60, 12)) {
+            let arena = ArenaTree::from_link(&tree);
+            let values = (0..arena.len()).map(|id| arena[id]).collect();
+            let parents: Vec<_> = (0..arena.len())
+                .map(|id| arena.parent(id).map(|parent| (parent, arena.left(parent) == Some(id))))
+                .collect();
+
+            prop_assert_eq!(from_parent_array(values, &parents).unwrap(), tree);
+        }
+
+        #[test]
+        fn shuffled_traversals_are_rejected_or_consistent(
+            tree in arb_tree(0..1, 30, 8),
+            swap_with in any::<prop::sample::Index>(),
+        ) {
+            let tree = with_distinct_values(&tree);
+            let pre = values(preorder(&tree));
+            let mut ino = values(inorder(&tree));
+            if !ino.is_empty() {
+                let index = swap_with.index(ino.len());
+                ino.swap(0, index);
+            }
+
+            // whatever is built must reproduce the given traversals
+            if let Ok(rebuilt) = from_preorder_inorder(&pre, &ino) {
+                prop_assert_eq!(values(preorder(&rebuilt)), pre);
+                prop_assert_eq!(values(inorder(&rebuilt)), ino);
+            }
+        }
+    }
+}
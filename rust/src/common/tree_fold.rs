@@ -0,0 +1,346 @@
+//! Folds and maps over binary trees, written with explicit stacks and queues.
+
+use std::cell::{Ref, RefCell};
+use std::rc::Rc;
+
+use crate::common::binary_tree::{postorder, preorder, NodeLink, TreeNode};
+
+/// build a tree of the same shape with every value transformed by `f` (called in pre-order)
+pub fn map_values<T, U>(root: &NodeLink<T>, mut f: impl FnMut(&T) -> U) -> NodeLink<U> {
+    let mut map_node = |node: &Rc<RefCell<TreeNode<T>>>| {
+        Rc::new(RefCell::new(TreeNode::new(f(&node.borrow().val))))
+    };
+
+    let root = root.as_ref()?;
+    let root_copy = map_node(root);
+
+    let mut stack = vec![(root.clone(), root_copy.clone())];
+    while let Some((original, copy)) = stack.pop() {
+        let original = original.borrow();
+        let mut copy = copy.borrow_mut();
+
+        if let Some(left) = &original.left {
+            let left_copy = map_node(left);
+            copy.left = Some(left_copy.clone());
+            stack.push((left.clone(), left_copy));
+        }
+        if let Some(right) = &original.right {
+            let right_copy = map_node(right);
+            copy.right = Some(right_copy.clone());
+            stack.push((right.clone(), right_copy));
+        }
+    }
+
+    Some(root_copy)
+}
+
+/// compute a value for every node from its own value and the values of its children
+/// (`None` for a missing child). Returns the value of the root
+pub fn fold_postorder<T, A>(
+    root: &NodeLink<T>,
+    mut f: impl FnMut(&T, Option<A>, Option<A>) -> A,
+) -> Option<A> {
+    // results of the subtrees which parents aren't visited yet
+    let mut results: Vec<A> = vec![];
+
+    for node in postorder(root) {
+        let node_ref = node.borrow();
+        // the right subtree is visited after the left one, so its result is on top
+        let right = node_ref.right.as_ref().and_then(|_| results.pop());
+        let left = node_ref.left.as_ref().and_then(|_| results.pop());
+        results.push(f(&node_ref.val, left, right));
+    }
+
+    results.pop()
+}
+
+/// fold the tree level by level from the root down,
+/// passing the values of each level from left to right
+pub fn fold_levels<T, A>(root: &NodeLink<T>, init: A, mut f: impl FnMut(A, &[&T]) -> A) -> A {
+    let mut acc = init;
+    let mut level: Vec<Rc<RefCell<TreeNode<T>>>> = root.iter().cloned().collect();
+
+    while !level.is_empty() {
+        let next_level = {
+            let refs: Vec<Ref<TreeNode<T>>> = level.iter().map(|node| node.borrow()).collect();
+            let values: Vec<&T> = refs.iter().map(|node_ref| &node_ref.val).collect();
+            acc = f(acc, &values);
+
+            refs.iter()
+                .flat_map(|node_ref| node_ref.left.iter().chain(node_ref.right.iter()))
+                .cloned()
+                .collect()
+        };
+        level = next_level;
+    }
+
+    acc
+}
+
+/// combine two trees into one having the union of their shapes.
+/// `f` gets the values of the nodes at the same position (`None` where a tree has no node)
+pub fn zip_with<A, B, C>(
+    left: &NodeLink<A>,
+    right: &NodeLink<B>,
+    mut f: impl FnMut(Option<&A>, Option<&B>) -> C,
+) -> NodeLink<C> {
+    let mut zip_nodes = |a: &NodeLink<A>, b: &NodeLink<B>| {
+        let a_ref = a.as_ref().map(|node| node.borrow());
+        let b_ref = b.as_ref().map(|node| node.borrow());
+        let val = f(
+            a_ref.as_ref().map(|node_ref| &node_ref.val),
+            b_ref.as_ref().map(|node_ref| &node_ref.val),
+        );
+        Rc::new(RefCell::new(TreeNode::new(val)))
+    };
+
+    if left.is_none() && right.is_none() {
+        return None;
+    }
+    let root = zip_nodes(left, right);
+
+    let mut stack = vec![(left.clone(), right.clone(), root.clone())];
+    while let Some((a, b, node)) = stack.pop() {
+        let (a_left, a_right) = match &a {
+            Some(a) => (a.borrow().left.clone(), a.borrow().right.clone()),
+            None => (None, None),
+        };
+        let (b_left, b_right) = match &b {
+            Some(b) => (b.borrow().left.clone(), b.borrow().right.clone()),
+            None => (None, None),
+        };
+
+        let mut node_ref = node.borrow_mut();
+        if a_left.is_some() || b_left.is_some() {
+            let child = zip_nodes(&a_left, &b_left);
+            node_ref.left = Some(child.clone());
+            stack.push((a_left, b_left, child));
+        }
+        if a_right.is_some() || b_right.is_some() {
+            let child = zip_nodes(&a_right, &b_right);
+            node_ref.right = Some(child.clone());
+            stack.push((a_right, b_right, child));
+        }
+    }
+
+    Some(root)
+}
+
+/// number of levels in the tree (0 for an empty one)
+pub fn height<T>(root: &NodeLink<T>) -> usize {
+    fold_levels(root, 0, |height, _| height + 1)
+}
+
+/// number of nodes in the tree
+pub fn node_count<T>(root: &NodeLink<T>) -> usize {
+    preorder(root).count()
+}
+
+/// number of edges on the longest path between any two nodes
+pub fn diameter<T>(root: &NodeLink<T>) -> usize {
+    // (height of the subtree, diameter of the subtree)
+    let result = fold_postorder(root, |_, left: Option<(usize, usize)>, right| {
+        let (left_height, left_diameter) = left.unwrap_or((0, 0));
+        let (right_height, right_diameter) = right.unwrap_or((0, 0));
+
+        let height = left_height.max(right_height) + 1;
+        let diameter = (left_height + right_height)
+            .max(left_diameter)
+            .max(right_diameter);
+        (height, diameter)
+    });
+
+    result.map(|(_, diameter)| diameter).unwrap_or(0)
+}
+
+/// check if heights of the subtrees of every node differ by at most 1
+pub fn is_balanced<T>(root: &NodeLink<T>) -> bool {
+    // height of the subtree if it's balanced
+    let result = fold_postorder(root, |_, left: Option<Option<usize>>, right| {
+        let left_height = left.unwrap_or(Some(0))?;
+        let right_height = right.unwrap_or(Some(0))?;
+
+        (left_height.abs_diff(right_height) <= 1).then_some(left_height.max(right_height) + 1)
+    });
+
+    result != Some(None)
+}
+
+/// widths of the tree levels from the root down. A width of a level is the length between
+/// its leftmost and rightmost nodes, counting the positions of missing nodes in between.
+/// Widths which don't fit into `usize` wrap around
+pub fn level_widths<T>(root: &NodeLink<T>) -> Vec<usize> {
+    let mut widths = vec![];
+    // nodes of the current level paired with their positions in it
+    let mut level: Vec<(usize, Rc<RefCell<TreeNode<T>>>)> =
+        root.iter().map(|node| (0, node.clone())).collect();
+
+    while let (Some((first, _)), Some((last, _))) = (level.first(), level.last()) {
+        // positions are shifted so that the leftmost node of a level is at 0
+        let offset = *first;
+        widths.push(last.wrapping_sub(offset).wrapping_add(1));
+
+        level = level
+            .iter()
+            .flat_map(|(position, node)| {
+                let position = position.wrapping_sub(offset).wrapping_mul(2);
+                let node_ref = node.borrow();
+                let left = node_ref.left.clone().map(|child| (position, child));
+                let right = node_ref
+                    .right
+                    .clone()
+                    .map(|child| (position.wrapping_add(1), child));
+                left.into_iter().chain(right)
+            })
+            .collect();
+    }
+
+    widths
+}
+
+/// the largest width among the tree levels (0 for an empty tree)
+pub fn max_width<T>(root: &NodeLink<T>) -> usize {
+    level_widths(root).into_iter().max().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::binary_tree::strategies::arb_tree;
+    use crate::common::binary_tree::{format_tree, level_order};
+    use crate::tree;
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    fn recursive_height(root: &NodeLink<i32>) -> usize {
+        match root {
+            None => 0,
+            Some(node) => {
+                let node_ref = node.borrow();
+                1 + recursive_height(&node_ref.left).max(recursive_height(&node_ref.right))
+            }
+        }
+    }
+
+    fn recursive_is_balanced(root: &NodeLink<i32>) -> bool {
+        match root {
+            None => true,
+            Some(node) => {
+                let node_ref = node.borrow();
+                recursive_height(&node_ref.left).abs_diff(recursive_height(&node_ref.right)) <= 1
+                    && recursive_is_balanced(&node_ref.left)
+                    && recursive_is_balanced(&node_ref.right)
+            }
+        }
+    }
+
+    fn degenerate_tree(depth: i32) -> NodeLink<i32> {
+        let mut root = None;
+        for val in (0..depth).rev() {
+            root = TreeNode::new_link(val, None, root);
+        }
+        root
+    }
+
+    #[rstest]
+    #[case(tree![], 0, 0, 0, 0, true)]
+    #[case(tree![1], 1, 1, 0, 1, true)]
+    #[case(tree![1, 2, 3, 4, 5], 5, 3, 3, 2, true)]
+    #[case(tree![3, 9, 20, null, null, 15, 7], 5, 3, 3, 2, true)]
+    #[case(tree![1, 2, 2, 3, 3, null, null, 4, 4], 7, 4, 4, 2, false)]
+    #[case(tree![1, 3, 2, 5, 3, null, 9], 6, 3, 4, 4, true)]
+    #[case(tree![1, 3, 2, 5, null, null, 9, 6, null, 7], 7, 4, 6, 7, false)]
+    fn metrics(
+        #[case] tree: NodeLink<i32>,
+        #[case] count: usize,
+        #[case] expected_height: usize,
+        #[case] expected_diameter: usize,
+        #[case] width: usize,
+        #[case] balanced: bool,
+    ) {
+        assert_eq!(node_count(&tree), count);
+        assert_eq!(height(&tree), expected_height);
+        assert_eq!(diameter(&tree), expected_diameter);
+        assert_eq!(max_width(&tree), width);
+        assert_eq!(is_balanced(&tree), balanced);
+    }
+
+    #[test]
+    fn level_widths_count_missing_nodes() {
+        let tree: NodeLink<i32> = tree![1, 3, 2, 5, null, null, 9, 6, null, 7];
+        assert_eq!(level_widths(&tree), vec![1, 2, 4, 7]);
+    }
+
+    #[test]
+    fn map_keeps_shape() {
+        let tree: NodeLink<i32> = tree![1, 2, 3, null, 4];
+        let mapped = map_values(&tree, |val| format!("v{}", val));
+
+        assert_eq!(format_tree(&mapped), "[v1,v2,v3,null,v4]");
+    }
+
+    #[test]
+    fn zip_merges_shapes() {
+        let left: NodeLink<i32> = tree![1, 3, 2, 5];
+        let right: NodeLink<i32> = tree![2, 1, 3, null, 4, null, 7];
+
+        let merged = zip_with(&left, &right, |a, b| a.unwrap_or(&0) + b.unwrap_or(&0));
+        assert_eq!(format_tree(&merged), "[3,4,5,5,4,null,7]");
+
+        let empty: NodeLink<i32> = None;
+        assert_eq!(zip_with(&empty, &empty, |_, _| 0), None);
+    }
+
+    #[test]
+    fn folds_visit_all_nodes() {
+        let tree: NodeLink<i32> = tree![1, 2, 3, 4, 5, null, 6];
+
+        let sum = fold_postorder(&tree, |val, left, right| {
+            val + left.unwrap_or(0) + right.unwrap_or(0)
+        });
+        assert_eq!(sum, Some(21));
+
+        let levels = fold_levels(&tree, vec![], |mut levels, level| {
+            levels.push(level.iter().map(|val| **val).collect::<Vec<_>>());
+            levels
+        });
+        assert_eq!(levels, vec![vec![1], vec![2, 3], vec![4, 5, 6]]);
+    }
+
+    #[test]
+    fn deep_tree() {
+        let depth = 100_000;
+        let tree = degenerate_tree(depth);
+
+        assert_eq!(node_count(&tree), depth as usize);
+        assert_eq!(height(&tree), depth as usize);
+        assert_eq!(diameter(&tree), depth as usize - 1);
+        assert_eq!(max_width(&tree), 1);
+        assert!(!is_balanced(&tree));
+
+        let doubled = map_values(&tree, |val| val * 2);
+        let zipped = zip_with(&tree, &doubled, |a, b| b.unwrap() - a.unwrap());
+        assert!(zipped == tree);
+    }
+
+    proptest! {
+        #[test]
+        fn metrics_match_recursive(tree in arb_tree(0..10, 60, 12)) {
+            let depths: Vec<usize> = level_order(&tree).map(|(depth, _)| depth).collect();
+
+            prop_assert_eq!(height(&tree), recursive_height(&tree));
+            prop_assert_eq!(node_count(&tree), depths.len());
+            prop_assert_eq!(is_balanced(&tree), recursive_is_balanced(&tree));
+            prop_assert_eq!(level_widths(&tree).len(), height(&tree));
+            prop_assert!(diameter(&tree) + 1 >= height(&tree));
+        }
+
+        #[test]
+        fn map_then_zip_restores_tree(tree in arb_tree(-100..100, 60, 12)) {
+            let negated = map_values(&tree, |val| -val);
+            let restored = zip_with(&tree, &negated, |a, b| a.unwrap() + b.unwrap() + a.unwrap());
+
+            prop_assert_eq!(restored, tree);
+        }
+    }
+}
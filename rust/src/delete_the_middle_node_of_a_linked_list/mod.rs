@@ -1,3 +1,5 @@
+use crate::common::linked_list::{ListCursor, ListExt};
+
 type ListNode = crate::common::linked_list::ListNode<i32>;
 pub struct Solution;
 
@@ -5,31 +7,13 @@ pub struct Solution;
 
 impl Solution {
     pub fn delete_middle(mut head: Option<Box<ListNode>>) -> Option<Box<ListNode>> {
-        if head.is_none() {
-            return None;
-        }
-
-        unsafe {
-            let mut ptr1: *mut Box<ListNode> = head.as_mut().unwrap();
-            if (*ptr1).next.is_none() {
-                return None;
-            }
-            let mut ptr2: *const Box<ListNode> = (*ptr1).next.as_ref().unwrap();
-
-            while let Some(next) = (*ptr2).next.as_ref() {
-                if let Some(next_next) = next.next.as_ref() {
-                    ptr2 = next_next;
-                } else {
-                    break;
-                }
-
-                ptr1 = (*ptr1).next.as_mut().unwrap();
-            }
+        let middle = head.len() / 2;
 
-            let ptr_mid: *mut Box<ListNode> = (*ptr1).next.as_mut().unwrap();
-            let ptr_after_mid = std::mem::replace(&mut (*ptr_mid).next, None);
-            (*ptr1).next = ptr_after_mid;
+        let mut cursor = ListCursor::new(&mut head);
+        for _ in 0..middle {
+            cursor.move_next();
         }
+        cursor.remove_next();
 
         head
     }
@@ -94,31 +94,26 @@ impl Bitset {
 #[cfg(test)]
 mod test {
     use super::Bitset;
+    use crate::design_test;
 
     #[test]
     fn case1() {
-        let count = 317;
-        let mut set = Bitset::new(count);
-
-        assert_eq!(set.count(), 0);
-        set.flip();
-        assert_eq!(set.count(), count);
-        assert!(set.all());
-        set.flip();
-        assert_eq!(set.count(), 0);
-
-        assert!(!set.one());
-        set.fix(0);
-        assert_eq!(set.count(), 1);
-        assert!(set.one());
-        set.fix(0);
-        assert_eq!(set.count(), 1);
-        set.fix(1);
-        assert_eq!(set.count(), 2);
-
-        set.unfix(1);
-        assert_eq!(set.count(), 1);
-        set.unfix(1);
-        assert_eq!(set.count(), 1);
+        design_test!(
+            Bitset { new(size), fix(idx), unfix(idx), flip(), all(), one(), count() },
+            r#"["Bitset","count","flip","count","all","flip","count","one","fix","count","one",
+                "fix","count","fix","count","unfix","count","unfix","count"]"#,
+            "[[317],[],[],[],[],[],[],[],[0],[],[],[0],[],[1],[],[1],[],[1],[]]",
+            "[null,0,null,317,true,null,0,false,null,1,true,null,1,null,2,null,1,null,1]",
+        );
+    }
+
+    #[test]
+    fn case2() {
+        design_test!(
+            Bitset { new(size), fix(idx), unfix(idx), flip(), all(), one(), count(), to_string() },
+            r#"["Bitset","fix","fix","flip","all","unfix","flip","one","unfix","count","toString"]"#,
+            "[[5],[3],[1],[],[],[0],[],[],[0],[],[]]",
+            r#"[null,null,null,null,false,null,null,true,null,2,"01010"]"#,
+        );
     }
 }
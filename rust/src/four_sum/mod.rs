@@ -76,31 +76,12 @@ impl Solution {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::vec2d;
+    use crate::{assert_eq_unordered, vec2d};
 
     fn case(nums: &Vec<i32>, target: i32, expected: &Vec<Vec<i32>>) {
-        let mut expected: Vec<Vec<i32>> = expected
-            .clone()
-            .into_iter()
-            .map(|v| {
-                let mut v_sorted = v;
-                v_sorted.sort();
-                v_sorted
-            })
-            .collect();
-        expected.sort();
-
-        let mut result: Vec<Vec<i32>> = Solution::four_sum(nums.clone(), target)
-            .into_iter()
-            .map(|v| {
-                let mut v_sorted = v;
-                v_sorted.sort();
-                v_sorted
-            })
-            .collect();
-        result.sort();
-
-        assert_eq!(result, expected);
+        let result = Solution::four_sum(nums.clone(), target);
+
+        assert_eq_unordered!(result, expected.clone(), ignore_inner_order);
     }
 
     #[test]
@@ -72,32 +72,16 @@ impl MagicDictionary {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::assert_returns;
-    use rstest::{fixture, rstest};
-
-    #[fixture]
-    fn words() -> Vec<String> {
-        vec!["hello", "leetcode"]
-            .into_iter()
-            .map(|s| s.to_owned())
-            .collect()
-    }
-
-    #[fixture]
-    fn full_dict(words: Vec<String>) -> MagicDictionary {
-        let mut dict = MagicDictionary::new();
-        dict.build_dict(words);
-        dict
-    }
-
-    #[rstest]
-    #[case("hellou", false)]
-    #[case("hello", false)]
-    #[case("hhllo", true)]
-    #[case("hell", false)]
-    #[case("leetcoded", false)]
-    #[case("leecode", false)]
-    fn case(full_dict: MagicDictionary, #[case] query: String, #[case] expected_result: bool) {
-        assert_returns!(expected_result, MagicDictionary::search, &full_dict, query);
+    use crate::design_test;
+
+    #[test]
+    fn case() {
+        design_test!(
+            MagicDictionary { new(), build_dict(dictionary), search(search_word) },
+            r#"["MagicDictionary","buildDict","search","search","search","search","search","search"]"#,
+            r#"[[],[["hello","leetcode"]],["hellou"],["hello"],["hhllo"],["hell"],["leetcoded"],
+                ["leecode"]]"#,
+            "[null,null,false,false,true,false,false,false]",
+        );
     }
 }
@@ -48,26 +48,15 @@ impl MyQueue {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::assert_returns;
+    use crate::design_test;
 
     #[test]
     fn case1() {
-        let mut queue = MyQueue::new();
-        assert_returns!(true, MyQueue::empty, &queue);
-
-        queue.push(1);
-        assert_returns!(false, MyQueue::empty, &queue);
-
-        queue.push(2);
-        assert_returns!(false, MyQueue::empty, &queue);
-
-        assert_returns!(1, MyQueue::peek, &mut queue);
-        assert_returns!(false, MyQueue::empty, &queue);
-
-        assert_returns!(1, MyQueue::pop, &mut queue);
-        assert_returns!(false, MyQueue::empty, &queue);
-
-        assert_returns!(2, MyQueue::pop, &mut queue);
-        assert_returns!(true, MyQueue::empty, &queue);
+        design_test!(
+            MyQueue { new(), push(x), pop(), peek(), empty() },
+            r#"["MyQueue","empty","push","empty","push","empty","peek","empty","pop","empty","pop","empty"]"#,
+            "[[],[],[1],[],[2],[],[],[],[],[],[],[]]",
+            "[null,true,null,false,null,false,1,false,1,false,2,true]",
+        );
     }
 }
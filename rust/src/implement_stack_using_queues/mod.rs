@@ -44,26 +44,15 @@ impl MyStack {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::assert_returns;
+    use crate::design_test;
 
     #[test]
     fn case1() {
-        let mut stack = MyStack::new();
-        assert_returns!(true, MyStack::empty, &stack);
-
-        stack.push(1);
-        assert_returns!(false, MyStack::empty, &stack);
-
-        stack.push(2);
-        assert_returns!(false, MyStack::empty, &stack);
-
-        assert_returns!(2, MyStack::top, &mut stack);
-        assert_returns!(false, MyStack::empty, &stack);
-
-        assert_returns!(2, MyStack::pop, &mut stack);
-        assert_returns!(false, MyStack::empty, &stack);
-
-        assert_returns!(1, MyStack::pop, &mut stack);
-        assert_returns!(true, MyStack::empty, &stack);
+        design_test!(
+            MyStack { new(), push(x), pop(), top(), empty() },
+            r#"["MyStack","empty","push","empty","push","empty","top","empty","pop","empty","pop","empty"]"#,
+            "[[],[],[1],[],[2],[],[],[],[],[],[],[]]",
+            "[null,true,null,false,null,false,2,false,2,false,1,true]",
+        );
     }
 }
@@ -1,19 +1,18 @@
+use crate::common::grid::Grid;
+
 pub struct Solution {}
 
 impl Solution {
-    pub fn fill_mines_grid(n: usize, mines: Vec<Vec<i32>>) -> Vec<bool> {
-        let mut mines_grid: Vec<bool> = vec![];
-        mines_grid.resize((n * n) as usize, false);
+    pub fn fill_mines_grid(n: usize, mines: Vec<Vec<i32>>) -> Grid<bool> {
+        let mut mines_grid = Grid::new(n, n, false);
         for mine in mines {
-            mines_grid[(mine[0] * n as i32 + mine[1]) as usize] = true;
+            mines_grid[(mine[0] as usize, mine[1] as usize)] = true;
         }
         mines_grid
     }
 
-    pub fn fill_orders_grid(n: usize) -> Vec<i32> {
-        let mut orders_grid: Vec<i32> = vec![];
-        orders_grid.resize(n * n, n as i32);
-        orders_grid
+    pub fn fill_orders_grid(n: usize) -> Grid<i32> {
+        Grid::new(n, n, n as i32)
     }
 
     pub fn order_of_largest_plus_sign(n: i32, mines: Vec<Vec<i32>>) -> i32 {
@@ -28,25 +27,25 @@ impl Solution {
             let mut start = 0;
             while start < n {
                 // find segment containing only 1s
-                while start < n && mines_grid[y * n + start] {
-                    orders_grid[y * n + start] = 0;
+                while start < n && mines_grid[(y, start)] {
+                    orders_grid[(y, start)] = 0;
                     start += 1;
                 }
                 if start == n {
                     break;
                 }
                 let mut end = start + 1;
-                while end < n && !mines_grid[(y * n + end) as usize] {
+                while end < n && !mines_grid[(y, end)] {
                     end += 1;
                 }
 
                 for x in start..(start + end) / 2 {
-                    let order = &mut orders_grid[(y * n + x) as usize];
+                    let order = &mut orders_grid[(y, x)];
                     *order = (*order).min((x - start + 1) as i32);
                 }
 
                 for x in (start + end) / 2..end {
-                    let order = &mut orders_grid[(y * n + x) as usize];
+                    let order = &mut orders_grid[(y, x)];
                     *order = (*order).min((end - x) as i32);
                 }
 
@@ -58,24 +57,24 @@ impl Solution {
         for x in 0..n {
             let mut start = 0;
             while start < n {
-                while start < n && mines_grid[(start * n + x) as usize] {
+                while start < n && mines_grid[(start, x)] {
                     start += 1;
                 }
                 if start == n {
                     break;
                 }
                 let mut end = start + 1;
-                while end < n && !mines_grid[(end * n + x) as usize] {
+                while end < n && !mines_grid[(end, x)] {
                     end += 1;
                 }
 
                 for y in start..(start + end) / 2 {
-                    let order = &mut orders_grid[(y * n + x) as usize];
+                    let order = &mut orders_grid[(y, x)];
                     *order = (*order).min((y - start + 1) as i32);
                 }
 
                 for y in (start + end) / 2..end {
-                    let order = &mut orders_grid[(y * n + x) as usize];
+                    let order = &mut orders_grid[(y, x)];
                     *order = (*order).min((end - y) as i32);
                 }
 
@@ -83,7 +82,7 @@ impl Solution {
             }
         }
 
-        orders_grid.iter().max().unwrap().clone()
+        *orders_grid.iter().max().unwrap()
     }
 }
 
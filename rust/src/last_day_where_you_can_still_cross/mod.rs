@@ -1,26 +1,15 @@
 use crate::common::disjoint_sets_union::{HashMapDSU, UnionFind};
+use crate::common::grid::Grid;
 
 struct Solution;
 
 impl Solution {
-    fn neighbors(row: i32, col: i32) -> [(i32, i32); 8] {
-        [
-            (row - 1, col - 1),
-            (row - 1, col),
-            (row - 1, col + 1),
-            (row, col - 1),
-            (row, col + 1),
-            (row + 1, col - 1),
-            (row + 1, col),
-            (row + 1, col + 1),
-        ]
-    }
-
     // top and bottom are disconnected if left and right sides are 8-connected by water
     // the approach is to use DSU to connect water cells day by day
     // and check if at the end of the day left and right sides are connected by water
     pub fn latest_day_to_cross(rows: i32, cols: i32, cells: Vec<Vec<i32>>) -> i32 {
         let ndays = cells.len();
+        let mut water = Grid::new(rows as usize, cols as usize, false);
 
         // dummy cell to which every water cell on the left side is connected
         let left_id = (rows * cols + 1) as usize;
@@ -29,29 +18,23 @@ impl Solution {
 
         let mut uf = HashMapDSU::new();
 
-        for (day, (row, col)) in cells.into_iter().map(|v| (v[0] - 1, v[1] - 1)).enumerate() {
-            let id = (row * cols + col) as usize;
+        for (day, cell) in cells.into_iter().enumerate() {
+            let (row, col) = ((cell[0] - 1) as usize, (cell[1] - 1) as usize);
+            let id = water.index_of(row, col);
+            water[(row, col)] = true;
             uf.insert(id);
 
-            for (n_row, n_col) in Solution::neighbors(row, col) {
-                if n_row < 0 || n_row >= rows || n_col < 0 || n_col >= cols {
-                    continue;
-                }
-
-                let n_id = (n_row * cols + n_col) as usize;
-
-                if !uf.contains(n_id) {
-                    continue;
+            for (n_row, n_col) in water.neighbors8(row, col) {
+                if water[(n_row, n_col)] {
+                    uf.join(id, water.index_of(n_row, n_col));
                 }
-
-                uf.join(id, n_id);
             }
 
             if col == 0 {
                 uf.join(left_id, id);
             }
 
-            if col == cols - 1 {
+            if col == water.cols() - 1 {
                 uf.join(right_id, id);
             }
 
@@ -111,17 +94,7 @@ mod test {
     #[test]
     fn case4() {
         assert_eq!(
-            Solution::latest_day_to_cross(
-                3,
-                3,
-                vec2d![
-                    [1, 1],
-                    [2, 1],
-                    [3, 1],
-                    [1, 3],
-                    [2, 3]
-                ]
-            ),
+            Solution::latest_day_to_cross(3, 3, vec2d![[1, 1], [2, 1], [3, 1], [1, 3], [2, 3]]),
             5
         );
     }
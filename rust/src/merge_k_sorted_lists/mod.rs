@@ -59,30 +59,32 @@ impl Solution {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::common::linked_list;
-
-    #[test]
-    fn case1() {
-        let input_values = vec![vec![1, 4, 5], vec![1, 3, 4], vec![2, 6]];
-        let expected_values = vec![1, 1, 2, 3, 4, 4, 5, 6];
-
-        let input_list: Vec<_> = input_values
-            .into_iter()
-            .map(|vals| linked_list::vec_to_list(vals))
-            .collect();
-
-        let result_list = Solution::merge_k_lists(input_list);
-
-        let result_values = linked_list::list_to_vec(result_list);
-
-        assert_eq!(result_values, expected_values);
+    use crate::assert_eq;
+    use crate::common::linked_list::strategies::arb_sorted_lists;
+    use crate::common::linked_list::{list_to_vec, parse_list, parse_lists, ListExt};
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("[[1,4,5],[1,3,4],[2,6]]", "[1,1,2,3,4,4,5,6]")]
+    #[case("[]", "[]")]
+    #[case("[[]]", "[]")]
+    #[case("[[],[-1],[]]", "[-1]")]
+    fn it_works(#[case] input: &str, #[case] expected: &str) {
+        let lists = parse_lists(input).unwrap();
+        let expected = parse_list(expected).unwrap();
+
+        let merged = Solution::merge_k_lists(lists);
+        assert_eq!(merged, expected);
     }
 
-    #[test]
-    fn empty_input() {
-        let empty_input = vec![None];
-        let result = Solution::merge_k_lists(empty_input);
+    proptest! {
+        #[test]
+        fn matches_sorted_concatenation(lists in arb_sorted_lists(-100..100, 6, 10)) {
+            let mut expected: Vec<i32> = lists.iter().flat_map(|list| list.values().copied()).collect();
+            expected.sort();
 
-        assert_eq!(result, None);
+            prop_assert_eq!(list_to_vec(Solution::merge_k_lists(lists)), expected);
+        }
     }
 }
@@ -1,28 +1,20 @@
+use crate::common::linked_list::{CollectList, ListExt};
+
 type ListNode = crate::common::linked_list::ListNode<i32>;
 pub struct Solution {}
 
 /////////////////////////////////////////////////////
 
 impl Solution {
-    pub fn merge_nodes(mut input_head: Option<Box<ListNode>>) -> Option<Box<ListNode>> {
-        input_head = input_head.unwrap().next;
-
-        let mut current_sum = 0;
-
-        let mut result_head = Box::new(ListNode::new(0));
-        let mut result_tail = &mut result_head;
-
-        while let Some(node) = input_head {
-            current_sum += node.val;
-            if node.val == 0 {
-                result_tail.next = Some(Box::new(ListNode::new(current_sum)));
-                result_tail = result_tail.next.as_mut().unwrap();
-                current_sum = 0;
-            }
-            input_head = node.next;
-        }
-
-        result_head.next
+    pub fn merge_nodes(head: Option<Box<ListNode>>) -> Option<Box<ListNode>> {
+        head.into_values()
+            .skip(1)
+            .scan(0, |sum, val| {
+                *sum += val;
+                Some((val == 0).then(|| std::mem::take(sum)))
+            })
+            .flatten()
+            .collect_list()
     }
 }
 
@@ -1,32 +1,16 @@
+use crate::common::linked_list::{CollectList, ListExt};
+
 type ListNode = crate::common::linked_list::ListNode<i32>;
 pub struct Solution;
 
 ///////////////////////////////////////////////////
 
 impl Solution {
-    pub fn partition(mut head: Option<Box<ListNode>>, x: i32) -> Option<Box<ListNode>> {
-        let mut head1 = ListNode::new(0);
-        let mut tail1 = &mut head1;
-
-        let mut head2 = ListNode::new(0);
-        let mut tail2 = &mut head2;
-
-        while let Some(mut head_) = head {
-            head = std::mem::replace(&mut head_.next, None);
-
-            if head_.val < x {
-                tail1.next = Some(head_);
-                tail1 = tail1.next.as_mut().unwrap();
-            } else {
-                tail2.next = Some(head_);
-                tail2 = tail2.next.as_mut().unwrap();
-            }
-        }
-
-        // link second list to the first one
-        tail1.next = head2.next;
+    pub fn partition(head: Option<Box<ListNode>>, x: i32) -> Option<Box<ListNode>> {
+        let less = head.values().filter(|&&val| val < x);
+        let rest = head.values().filter(|&&val| val >= x);
 
-        head1.next
+        less.chain(rest).copied().collect_list()
     }
 }
 
@@ -35,8 +19,10 @@ impl Solution {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use rstest::rstest;
     use crate::common::linked_list;
+    use crate::common::linked_list::strategies::arb_list;
+    use proptest::prelude::*;
+    use rstest::rstest;
 
     #[rstest]
     #[case(vec![1,4,3,2,5,2], 3, vec![1,2,2,4,3,5])]
@@ -51,4 +37,16 @@ mod tests {
         let result = linked_list::list_to_vec(result_list);
         assert_eq!(result, expected_result);
     }
+
+    proptest! {
+        #[test]
+        fn matches_stable_partition(list in arb_list(-10..10, 30), pivot in -10..10) {
+            let values: Vec<i32> = list.values().copied().collect();
+            let mut expected: Vec<i32> = values.iter().copied().filter(|&val| val < pivot).collect();
+            expected.extend(values.iter().copied().filter(|&val| val >= pivot));
+
+            let result = linked_list::list_to_vec(Solution::partition(list, pivot));
+            prop_assert_eq!(result, expected);
+        }
+    }
 }
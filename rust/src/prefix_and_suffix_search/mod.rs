@@ -122,15 +122,31 @@ type WordFilter = WordFilter2;
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::design_test;
 
     #[test]
     fn it_works() {
-        let words = vec!["lol", "apple", "kek", "ape", "chebureck"]
-            .into_iter()
-            .map(String::from)
-            .collect();
-        let filter = WordFilter::new(words);
+        design_test!(
+            WordFilter { new(words), f(pref, suff) },
+            r#"["WordFilter","f"]"#,
+            r#"[[["lol","apple","kek","ape","chebureck"]],["a","e"]]"#,
+            "[null,3]",
+        );
+    }
 
-        assert_eq!(filter.f("a".into(), "e".into()), 3);
+    #[test]
+    fn example() {
+        design_test!(
+            WordFilter1 as "WordFilter" { new(words), f(pref, suff) },
+            r#"["WordFilter","f"]"#,
+            r#"[[["apple"]],["a","e"]]"#,
+            "[null,0]",
+        );
+        design_test!(
+            WordFilter2 as "WordFilter" { new(words), f(pref, suff) },
+            r#"["WordFilter","f"]"#,
+            r#"[[["apple"]],["a","e"]]"#,
+            "[null,0]",
+        );
     }
 }
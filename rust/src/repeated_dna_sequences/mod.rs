@@ -54,13 +54,14 @@ impl Solution {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::assert_eq_unordered;
 
     #[test]
     fn case1() {
         let s = "AAAAACCCCCAAAAACCCCCCAAAAAGGGTTT".to_string();
         let expected = vec!["AAAAACCCCC", "CCCCCAAAAA"];
         let result = Solution::find_repeated_dna_sequences(s);
-        assert_eq!(expected, result);
+        assert_eq_unordered!(result, expected);
     }
 
     #[test]
@@ -68,6 +69,6 @@ mod test {
         let s = "AAAAAAAAAAAAA".to_string();
         let expected = vec!["AAAAAAAAAA"];
         let result = Solution::find_repeated_dna_sequences(s);
-        assert_eq!(expected, result);
+        assert_eq_unordered!(result, expected);
     }
 }
@@ -59,7 +59,7 @@ impl Solution2 {
 
 #[cfg(test)]
 mod tests {
-    use crate::{vec2d, common::parse_2d_array};
+    use crate::{vec2d, common::leetcode_format::FromLeetcode};
     use super::*;
 
     #[test]
@@ -77,8 +77,8 @@ mod tests {
         let input_str = "[[5,1,9,11],[2,4,8,10],[13,3,6,7],[15,14,12,16]]";
         let expected_str = "[[15,13,2,5],[14,3,4,1],[12,6,8,9],[16,7,10,11]]";
 
-        let mut matrix = parse_2d_array(input_str);
-        let expected = parse_2d_array(expected_str);
+        let mut matrix = Vec::<Vec<i32>>::from_leetcode(input_str).unwrap();
+        let expected = Vec::<Vec<i32>>::from_leetcode(expected_str).unwrap();
 
         Solution::rotate(&mut matrix);
 
@@ -100,8 +100,8 @@ mod tests {
         let input_str = "[[5,1,9,11],[2,4,8,10],[13,3,6,7],[15,14,12,16]]";
         let expected_str = "[[15,13,2,5],[14,3,4,1],[12,6,8,9],[16,7,10,11]]";
 
-        let mut matrix = parse_2d_array(input_str);
-        let expected = parse_2d_array(expected_str);
+        let mut matrix = Vec::<Vec<i32>>::from_leetcode(input_str).unwrap();
+        let expected = Vec::<Vec<i32>>::from_leetcode(expected_str).unwrap();
 
         Solution2::rotate(&mut matrix);
 
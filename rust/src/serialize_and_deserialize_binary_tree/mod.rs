@@ -80,6 +80,10 @@ impl Codec {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::assert_eq;
+    use crate::common::binary_tree::strategies::arb_tree;
+    use crate::tree;
+    use proptest::{prop_assert_eq, proptest};
     use rstest::{fixture, rstest};
     type NodeLink = crate::common::binary_tree::NodeLink<i32>;
 
@@ -90,32 +94,7 @@ mod tests {
 
     #[fixture]
     fn expected_tree() -> NodeLink {
-        TreeNode::new_link(
-            5, // root
-            TreeNode::new_link(
-                1, // left
-                None,
-                TreeNode::new_link(
-                    3, // left.right,
-                    None, None,
-                ),
-            ),
-            TreeNode::new_link(
-                9, // right
-                TreeNode::new_link(
-                    7, // right.left
-                    None, None,
-                ),
-                TreeNode::new_link(
-                    15, // right.right
-                    None,
-                    TreeNode::new_link(
-                        20, // right.right.right
-                        None, None,
-                    ),
-                ),
-            ),
-        )
+        tree!(5, (1, _, 3), (9, 7, (15, _, 20)))
     }
 
     #[fixture]
@@ -134,4 +113,15 @@ mod tests {
         let deserialized = codec.deserialize(expected_string);
         assert_eq!(deserialized, expected_tree);
     }
+
+    proptest! {
+        #[test]
+        fn serialize_deserialize(tree in arb_tree(-1000..1000, 50, 10)) {
+            let codec = Codec::new();
+            let serialized = codec.serialize(tree.clone());
+            let deserialized = codec.deserialize(serialized);
+
+            prop_assert_eq!(deserialized, tree);
+        }
+    }
 }
@@ -40,22 +40,25 @@ impl SnapshotArray {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::design_test;
 
     #[test]
     fn it_works() {
-        let length = 5;
-        let mut obj = SnapshotArray::new(length);
-        obj.set(0, 3);
-        assert_eq!(obj.snap(), 0);
-
-        obj.set(0, 2);
-        assert_eq!(obj.snap(), 1);
-
-        obj.set(0, 1);
-        assert_eq!(obj.snap(), 2);
+        design_test!(
+            SnapshotArray { new(length), set(index, val), snap(), get(index, snap_id) },
+            r#"["SnapshotArray","set","snap","set","snap","set","snap","get","get","get"]"#,
+            "[[5],[0,3],[],[0,2],[],[0,1],[],[0,1],[0,2],[0,0]]",
+            "[null,null,0,null,1,null,2,2,1,3]",
+        );
+    }
 
-        assert_eq!(obj.get(0, 1), 2);
-        assert_eq!(obj.get(0, 2), 1);
-        assert_eq!(obj.get(0, 0), 3);
+    #[test]
+    fn example() {
+        design_test!(
+            SnapshotArray { new(length), set(index, val), snap(), get(index, snap_id) },
+            r#"["SnapshotArray","set","snap","set","get"]"#,
+            "[[3],[0,5],[],[0,6],[0,0]]",
+            "[null,null,0,null,5]",
+        );
     }
 }
@@ -53,40 +53,17 @@ impl StreamChecker {
 #[cfg(test)]
 mod tests {
     use super::StreamChecker;
-    use crate::assert_returns;
-    use rstest::fixture;
-    use rstest::rstest;
-
-    #[fixture]
-    fn words() -> Vec<String> {
-        vec!["cd", "f", "kl"]
-            .iter()
-            .map(|&s| s.to_owned())
-            .collect()
-    }
-
-    #[fixture]
-    fn cases() -> Vec<(char, bool)> {
-        let chars = vec!['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l'];
-        let results = vec![
-            false, false, false, true, false, true, false, false, false, false, false, true,
-        ];
-        assert!(chars.len() == results.len());
-
-        chars.into_iter().zip(results).collect()
-    }
-
-    #[rstest]
-    fn it_works(words: Vec<String>, cases: Vec<(char, bool)>) {
-        let mut checker = StreamChecker::new(words);
-
-        for (sym, expected_return_value) in cases {
-            assert_returns!(
-                expected_return_value,
-                StreamChecker::query,
-                &mut checker,
-                sym
-            );
-        }
+    use crate::design_test;
+
+    #[test]
+    fn it_works() {
+        design_test!(
+            StreamChecker { new(words), query(letter) },
+            r#"["StreamChecker","query","query","query","query","query","query","query","query",
+                "query","query","query","query"]"#,
+            r#"[[["cd","f","kl"]],["a"],["b"],["c"],["d"],["e"],["f"],["g"],["h"],["i"],["j"],
+                ["k"],["l"]]"#,
+            "[null,false,false,false,true,false,true,false,false,false,false,false,true]",
+        );
     }
 }
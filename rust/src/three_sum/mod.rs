@@ -250,32 +250,27 @@ impl Solution {
 
 #[cfg(test)]
 mod tests {
-    use crate::vec2d;
+    use crate::{assert_eq_unordered, vec2d};
 
     use super::*;
 
     #[test]
     fn case1() {
         let nums = vec![-1, 0, 1, 2, -1, -4];
-        let mut expected = vec2d![[-1, -1, 2], [-1, 0, 1]];
-        expected.sort();
+        let expected = vec2d![[-1, -1, 2], [-1, 0, 1]];
 
-        let mut result = Solution::three_sum(nums);
-        result.sort();
+        let result = Solution::three_sum(nums);
 
-        assert_eq!(result, expected);
+        assert_eq_unordered!(result, expected);
     }
 
     #[test]
     fn case2() {
         let nums = vec![3, 0, -2, -1, 1, 2];
-        let mut expected = vec2d![[-2, -1, 3], [-2, 0, 2], [-1, 0, 1]];
+        let expected = vec2d![[-2, -1, 3], [-2, 0, 2], [-1, 0, 1]];
 
-        expected.sort();
+        let result = Solution::three_sum(nums);
 
-        let mut result = Solution::three_sum(nums);
-        result.sort();
-
-        assert_eq!(result, expected);
+        assert_eq_unordered!(result, expected);
     }
 }
@@ -1,103 +1,50 @@
+use crate::common::grid::Grid;
+
 pub struct Solution;
 //////////////////////////
 
-use std::collections::HashSet;
 type Board = Vec<Vec<char>>;
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone)]
-struct Point {
-    pub x: i32,
-    pub y: i32,
-}
-
-impl Point {
-    fn step_left(&self) -> Point {
-        Point {
-            x: self.x - 1,
-            y: self.y,
-        }
-    }
-    fn step_right(&self) -> Point {
-        Point {
-            x: self.x + 1,
-            y: self.y,
-        }
-    }
-    fn step_up(&self) -> Point {
-        Point {
-            x: self.x,
-            y: self.y - 1,
-        }
-    }
-    fn step_down(&self) -> Point {
-        Point {
-            x: self.x,
-            y: self.y + 1,
-        }
-    }
-}
-
 impl Solution {
     pub fn exist(board: Board, word: String) -> bool {
-        for start_y in 0..board.len() {
-            for start_x in 0..(board[0].len()) {
-                let mut visited: HashSet<Point> = HashSet::new();
-                let mut chars = word.chars().rev().collect();
-                if Solution::find_impl(
-                    &board,
-                    Point {
-                        x: start_x as i32,
-                        y: start_y as i32,
-                    },
-                    &mut chars,
-                    &mut visited,
-                ) {
-                    return true;
-                }
-            }
-        }
+        let board = Grid::try_from(board).expect("board must be rectangular");
+        let mut visited = Grid::new(board.rows(), board.cols(), false);
+        let mut chars = word.chars().rev().collect();
 
-        false
+        board
+            .coords()
+            .any(|pos| Solution::find_impl(&board, pos, &mut chars, &mut visited))
     }
 
     fn find_impl(
-        board: &Board,
-        pos: Point,
+        board: &Grid<char>,
+        pos: (usize, usize),
         chars_left: &mut Vec<char>,
-        visited: &mut HashSet<Point>,
+        visited: &mut Grid<bool>,
     ) -> bool {
         if chars_left.is_empty() {
             return true;
         }
 
-        if visited.contains(&pos) {
-            return false;
-        }
-
-        if pos.y < 0
-            || pos.x < 0
-            || pos.y as usize >= board.len()
-            || pos.x as usize >= board[0].len()
-        {
+        if visited[pos] {
             return false;
         }
 
         let next_char = *chars_left.last().unwrap();
 
-        if board[pos.y as usize][pos.x as usize] != next_char {
+        if board[pos] != next_char {
             return false;
         }
 
         chars_left.pop();
-        visited.insert(pos.clone());
+        visited[pos] = true;
 
-        let found = false
-            || Solution::find_impl(board, pos.step_left(), chars_left, visited)
-            || Solution::find_impl(board, pos.step_right(), chars_left, visited)
-            || Solution::find_impl(board, pos.step_up(), chars_left, visited)
-            || Solution::find_impl(board, pos.step_down(), chars_left, visited);
+        let found = chars_left.is_empty()
+            || board
+                .neighbors4(pos.0, pos.1)
+                .any(|next| Solution::find_impl(board, next, chars_left, visited));
 
-        visited.remove(&pos);
+        visited[pos] = false;
         chars_left.push(next_char);
 
         found
@@ -107,20 +54,19 @@ impl Solution {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{assert_returns, vec2d};
+    use crate::assert_returns;
     use rstest::rstest;
 
     #[rstest]
-    #[case(vec2d![["A","B","C","E"],["S","F","C","S"],["A","D","E","E"]], "ABCCED", true)]
-    #[case(vec2d![["A","B","C","E"],["S","F","C","S"],["A","D","E","E"]], "SEE", true)]
-    #[case(vec2d![["A","B","C","E"],["S","F","C","S"],["A","D","E","E"]], "ABCB", false)]
-    #[case(vec2d![["a","a","a"],    ["A","A","A"],    ["a","a","a"]],     "aAaaaAaaA", true)]
-    #[rstest]
-    fn it_works(#[case] board: Vec<Vec<&str>>, #[case] word: &str, #[case] expected_result: bool) {
-        let board = board
-            .into_iter()
-            .map(|row| row.into_iter().map(|s| s.chars().next().unwrap()).collect())
-            .collect();
+    #[case("ABCE\nSFCS\nADEE", "ABCCED", true)]
+    #[case("ABCE\nSFCS\nADEE", "SEE", true)]
+    #[case("ABCE\nSFCS\nADEE", "ABCB", false)]
+    #[case("aaa\nAAA\naaa", "aAaaaAaaA", true)]
+    #[case("A", "A", true)]
+    #[case("AB", "ABA", false)]
+    fn it_works(#[case] board: &str, #[case] word: &str, #[case] expected_result: bool) {
+        let board: Grid<char> = board.parse().unwrap();
+        let board: Board = board.into();
 
         assert_returns!(expected_result, Solution::exist, board, word.to_owned());
     }